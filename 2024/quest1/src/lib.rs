@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use utils::Solution;
+
+fn potions_needed(c: u8) -> Option<i64> {
+    match c {
+        b'A' => Some(0),
+        b'B' => Some(1),
+        b'C' => Some(3),
+        b'D' => Some(5),
+        _ => None,
+    }
+}
+
+pub struct Quest1;
+
+impl Solution for Quest1 {
+    type Input<'a> = Vec<Option<i64>>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.trim().bytes().map(potions_needed).collect()
+    }
+
+    fn part1(potions_needed: &Self::Input<'_>) -> impl Display {
+        potions_needed.iter().flatten().sum::<i64>()
+    }
+
+    fn part2(potions_needed: &Self::Input<'_>) -> impl Display {
+        potions_needed
+            .chunks_exact(2)
+            .map(|chunk| {
+                let total = chunk.iter().map(|x| x.unwrap_or(0)).sum::<i64>();
+                if chunk.iter().all(|x| x.is_some()) {
+                    total + 2
+                } else {
+                    total
+                }
+            })
+            .sum::<i64>()
+    }
+
+    fn part3(potions_needed: &Self::Input<'_>) -> impl Display {
+        potions_needed
+            .chunks_exact(3)
+            .map(|chunk| {
+                let total = chunk.iter().map(|x| x.unwrap_or(0)).sum::<i64>();
+                let monsters = chunk.iter().map(|x| i32::from(x.is_some())).sum();
+
+                match monsters {
+                    3 => total + 6,
+                    2 => total + 2,
+                    _ => total,
+                }
+            })
+            .sum::<i64>()
+    }
+}
+
+utils::quest!(1, Quest1);
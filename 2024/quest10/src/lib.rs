@@ -0,0 +1,374 @@
+use std::fmt::Display;
+
+use utils::{
+    grid::{Grid, Point2},
+    Solution,
+};
+
+fn try_find_shared_symbol(row: &[char], col: &[char]) -> Option<char> {
+    for &r in row {
+        for &c in col {
+            if r != '?' && r == c {
+                return Some(r);
+            }
+        }
+    }
+
+    None
+}
+
+fn runic_word(grid: &[Vec<char>]) -> String {
+    let rows: Vec<Vec<char>> = grid[2..6]
+        .iter()
+        .map(|row| row[0..2].iter().chain(row[6..8].iter()).copied().collect())
+        .collect();
+    let columns: Vec<Vec<char>> = (2..6)
+        .map(|i| {
+            grid[0..2]
+                .iter()
+                .chain(grid[6..8].iter())
+                .map(|row| row[i])
+                .collect()
+        })
+        .collect();
+
+    let mut word = String::new();
+
+    for row in rows.iter() {
+        for col in columns.iter() {
+            let char = try_find_shared_symbol(row, col).unwrap();
+            word.push(char);
+        }
+    }
+
+    word
+}
+
+fn runic_word_power(word: &str) -> u32 {
+    word.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let base = (c as u32) - ('A' as u32) + 1;
+            base * (i as u32 + 1)
+        })
+        .sum()
+}
+
+struct Wall {
+    grid: Grid<char>,
+    width_blocks: usize,
+    height_blocks: usize,
+}
+
+// Offset from the top-left of the block to where the inner 4x4 word starts.
+const WORD_OFFSET: Point2 = Point2::new(2, 2);
+
+// Offset along a row or column of the ith symbol on the block's border, which
+// has two symbols either side of the word.
+fn border_offset(i: usize) -> isize {
+    if i >= 2 {
+        i as isize + 4
+    } else {
+        i as isize
+    }
+}
+
+impl Wall {
+    // Adjacent blocks overlap by their two-symbol borders.
+    fn block_origin(x: usize, y: usize) -> Point2 {
+        Point2::new(x as isize, y as isize) * 6
+    }
+
+    fn get_grid_at(&self, x: usize, y: usize) -> Vec<Vec<char>> {
+        let origin = Self::block_origin(x, y);
+
+        (0..8)
+            .map(|r| {
+                (0..8)
+                    .map(|c| self.grid[origin + Point2::new(c, r)])
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn try_solve_at(&mut self, x: usize, y: usize) -> (Option<String>, bool) {
+        #[derive(Debug, Clone, Copy)]
+        enum Specifier {
+            Row(usize, usize),
+            Col(usize, usize),
+        }
+
+        let grid = self.get_grid_at(x, y);
+
+        let mut rows: Vec<Vec<char>> = grid[2..6]
+            .iter()
+            .map(|row| row[0..2].iter().chain(row[6..8].iter()).copied().collect())
+            .collect();
+        let mut columns: Vec<Vec<char>> = (2..6)
+            .map(|i| {
+                grid[0..2]
+                    .iter()
+                    .chain(grid[6..8].iter())
+                    .map(|row| row[i])
+                    .collect()
+            })
+            .collect();
+
+        // Quick sanity check
+        for row in rows.iter() {
+            if row.iter().filter(|&&x| x == '?').count() > 1 {
+                // row contains more than 1 ?, bail
+                return (None, false);
+            }
+        }
+        for col in columns.iter() {
+            if col.iter().filter(|&&x| x == '?').count() > 1 {
+                // column contains more than 1 ?, bail
+                return (None, false);
+            }
+        }
+
+        let mut empty = vec![vec![true; 4]; 4];
+        let mut word = Vec::new();
+        let mut used_chars = Vec::new();
+        let mut qmark_replacements = Vec::new();
+
+        loop {
+            let mut mutated_this_loop = false;
+
+            // Solve as much as possible.
+            for r in 0..4 {
+                for c in 0..4 {
+                    if !empty[r][c] {
+                        continue;
+                    }
+
+                    if let Some(char) = try_find_shared_symbol(&rows[r], &columns[c]) {
+                        word.push(((r, c), char));
+                        used_chars.push(char);
+                        empty[r][c] = false;
+                        mutated_this_loop = true;
+                    }
+                }
+            }
+
+            // Go through the remaining empty spots, for each one, try to find a
+            // character that would fit
+            for r in 0..4 {
+                for c in 0..4 {
+                    if !empty[r][c] {
+                        continue;
+                    }
+
+                    let candidate_chars = rows[r]
+                        .iter()
+                        .chain(columns[c].iter())
+                        .copied()
+                        .filter(|&x| x != '?')
+                        .filter(|x| !used_chars.contains(x))
+                        .filter(|x| {
+                            let row_contains = rows[r].contains(x);
+                            let col_contains = columns[c].contains(x);
+
+                            // exactly one of the row or the column contains this char
+                            row_contains ^ col_contains
+                        })
+                        .collect::<Vec<_>>();
+
+                    if candidate_chars.len() != 1 {
+                        continue;
+                    }
+
+                    let candidate_char = candidate_chars[0];
+                    let row_contains = rows[r].contains(&candidate_char);
+                    let col_contains = columns[c].contains(&candidate_char);
+
+                    if row_contains {
+                        let qmark_pos = match columns[c].iter().position(|&x| x == '?') {
+                            Some(pos) => pos,
+                            None => continue,
+                        };
+                        columns[c][qmark_pos] = candidate_char;
+
+                        qmark_replacements.push((Specifier::Col(c, qmark_pos), candidate_char))
+                    } else if col_contains {
+                        let qmark_pos = match rows[r].iter().position(|&x| x == '?') {
+                            Some(pos) => pos,
+                            None => continue,
+                        };
+                        rows[r][qmark_pos] = candidate_char;
+
+                        qmark_replacements.push((Specifier::Row(r, qmark_pos), candidate_char))
+                    } else {
+                        unreachable!()
+                    }
+
+                    word.push(((r, c), candidate_char));
+                    used_chars.push(candidate_char);
+                    empty[r][c] = false;
+
+                    mutated_this_loop = true;
+                }
+            }
+
+            if !mutated_this_loop {
+                break;
+            }
+        }
+
+        let mut mutated_overall = false;
+
+        // Make any qmark replacements we found on the actual grid
+        let origin = Self::block_origin(x, y);
+        for (specifier, char) in qmark_replacements {
+            let offset = match specifier {
+                Specifier::Row(r, i) => Point2::new(border_offset(i), r as isize + WORD_OFFSET.y),
+                Specifier::Col(c, i) => Point2::new(c as isize + WORD_OFFSET.x, border_offset(i)),
+            };
+            self.grid[origin + offset] = char;
+
+            mutated_overall = true;
+        }
+
+        if word.len() == 16 {
+            word.sort();
+
+            // Put the word onto the grid
+            for &((r, c), char) in word.iter() {
+                self.grid[origin + WORD_OFFSET + Point2::new(c as isize, r as isize)] = char;
+            }
+
+            (
+                Some(word.iter().map(|(_, c)| *c).collect()),
+                mutated_overall,
+            )
+        } else {
+            (None, mutated_overall)
+        }
+    }
+}
+
+pub struct Quest10;
+
+impl Solution for Quest10 {
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn part1(&input: &Self::Input<'_>) -> impl Display {
+        let grid: Vec<Vec<_>> = input
+            .trim()
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        runic_word(&grid)
+    }
+
+    fn part2(&input: &Self::Input<'_>) -> impl Display {
+        let grids: Vec<Vec<Vec<char>>> = input
+            .trim()
+            .split("\n\n")
+            .flat_map(|row_of_blocks| {
+                let internal_rows: Vec<Vec<&str>> = row_of_blocks
+                    .lines()
+                    .map(|line| line.split_whitespace().collect())
+                    .collect();
+
+                (0..internal_rows[0].len()).map(move |i| {
+                    internal_rows
+                        .iter()
+                        .map(|row| row[i].chars().collect())
+                        .collect()
+                })
+            })
+            .collect();
+
+        grids
+            .iter()
+            .map(|grid| {
+                let word = runic_word(grid);
+                runic_word_power(&word)
+            })
+            .sum::<u32>()
+    }
+
+    fn part3(&input: &Self::Input<'_>) -> impl Display {
+        let big_grid = Grid::parse(input.trim());
+        let width_blocks = big_grid.width() / 6;
+        let height_blocks = big_grid.height() / 6;
+
+        let mut wall = Wall {
+            grid: big_grid,
+            width_blocks,
+            height_blocks,
+        };
+
+        let mut solved = vec![vec![false; wall.width_blocks]; wall.height_blocks];
+        let mut total = 0;
+
+        loop {
+            let mut mutated_this_loop = false;
+
+            for (y, solved_row) in solved.iter_mut().enumerate() {
+                for (x, solved) in solved_row.iter_mut().enumerate() {
+                    if *solved {
+                        continue;
+                    }
+
+                    let (word, mutated) = wall.try_solve_at(x, y);
+
+                    if let Some(word) = word {
+                        *solved = true;
+                        mutated_this_loop = true;
+                        total += runic_word_power(&word);
+                    }
+
+                    if mutated {
+                        mutated_this_loop = true;
+                    }
+                }
+            }
+
+            if !mutated_this_loop {
+                break;
+            }
+        }
+
+        total
+    }
+}
+
+utils::quest!(
+    10,
+    Quest10,
+    ignore {
+        part3: "the puzzle's example wall isn't recorded; see tests::part3_wall",
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part3_wall() {
+        // Two blocks sharing their middle border. The left block's word is
+        // A to P in order, worth 1496. The right block needs the R hidden
+        // behind its ? before its word, CDQRGHSTKLUVOPWX, can be finished,
+        // and that's worth 2372.
+        let wall = "\
+**ABCD**CDQR**
+**EFGH**GHST**
+AB....CD....Q?
+EF....GH....ST
+IJ....KL....UV
+MN....OP....WX
+**IJKL**KLUV**
+**MNOP**OPWX**";
+
+        assert_eq!(Quest10::part3(&wall).to_string(), "3868");
+    }
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashMap, fmt::Display};
+
+use utils::{
+    counter::Counter,
+    recurrence::{Arithmetic, LinearRecurrence},
+    Solution,
+};
+
+fn parse_input(input: &str) -> HashMap<&str, Counter<&str>> {
+    let mut rules = HashMap::new();
+
+    for line in input.trim().lines() {
+        let (head, tail) = line.split_once(':').unwrap();
+
+        rules.insert(head, tail.split(',').collect());
+    }
+
+    rules
+}
+
+fn simulate<'a>(
+    model: &LinearRecurrence<&'a str>,
+    initial_pop: impl IntoIterator<Item = &'a str>,
+    days: u64,
+) -> i64 {
+    model
+        .total_after(
+            &initial_pop.into_iter().collect(),
+            days,
+            Arithmetic::Checked,
+        )
+        .expect("population is too large for an i64")
+}
+
+pub struct Quest11;
+
+impl Solution for Quest11 {
+    type Input<'a> = LinearRecurrence<&'a str>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        LinearRecurrence::from_rules(&parse_input(input))
+    }
+
+    fn part1(model: &Self::Input<'_>) -> impl Display {
+        simulate(model, ["A"], 4)
+    }
+
+    fn part2(model: &Self::Input<'_>) -> impl Display {
+        simulate(model, ["Z"], 10)
+    }
+
+    fn part3(model: &Self::Input<'_>) -> impl Display {
+        // Column i of the transitions after 20 days is the population grown
+        // from a single termite of the i-th kind, so its sum is that
+        // population's size.
+        let transitions = model
+            .transitions()
+            .pow(20, Arithmetic::Checked)
+            .expect("population is too large for an i64");
+
+        let values_for_initial_pops: Vec<_> = (0..transitions.size())
+            .map(|col| {
+                (0..transitions.size())
+                    .try_fold(0i64, |total, row| {
+                        total.checked_add(transitions[(row, col)])
+                    })
+                    .expect("population is too large for an i64")
+            })
+            .collect();
+
+        let min = values_for_initial_pops.iter().min().unwrap();
+        let max = values_for_initial_pops.iter().max().unwrap();
+
+        max - min
+    }
+}
+
+utils::quest!(
+    11,
+    Quest11,
+    ignore {
+        part2: "the puzzle gives no example for this part",
+    }
+);
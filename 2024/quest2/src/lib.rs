@@ -0,0 +1,116 @@
+use std::{collections::HashSet, fmt::Display};
+
+use utils::{
+    grid::{Grid, OrthoDir, Point2, Wrap, Wrapping},
+    Solution,
+};
+
+fn matches_overlapping(needle: &str, mut haystack: &str) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+
+    while let Some(i) = haystack.find(needle) {
+        matches.push(start + i);
+
+        let mut j = i + 1;
+        while !haystack.is_char_boundary(j) {
+            j += 1;
+        }
+
+        start += j;
+        haystack = &haystack[j..]
+    }
+
+    matches
+}
+
+pub struct Inscription<'a> {
+    needles: Vec<&'a str>,
+    haystack: &'a str,
+}
+
+impl<'a> Inscription<'a> {
+    fn parse(input: &'a str) -> Self {
+        let (words, haystack) = input.trim().split_once("\n\n").unwrap();
+        let needles = words.strip_prefix("WORDS:").unwrap().split(',').collect();
+
+        Self { needles, haystack }
+    }
+}
+
+fn get_word(
+    grid: Wrapping<char>,
+    start: Point2,
+    dir: OrthoDir,
+    len: usize,
+) -> (String, Vec<Point2>) {
+    let word_chars: Vec<_> = grid.walk(start, dir).take(len).collect();
+    let word = word_chars.iter().map(|&coords| grid[coords]).collect();
+
+    (word, word_chars)
+}
+
+pub struct Quest2;
+
+impl Solution for Quest2 {
+    type Input<'a> = Inscription<'a>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Inscription::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> impl Display {
+        input
+            .needles
+            .iter()
+            .map(|needle| matches_overlapping(needle, input.haystack).len())
+            .sum::<usize>()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> impl Display {
+        let haystack = input.haystack;
+        let mut is_runic_letter = vec![false; haystack.len()];
+
+        for &needle in input.needles.iter() {
+            let needle_rev = needle.chars().rev().collect::<String>();
+
+            for n in [needle, &needle_rev] {
+                for i in matches_overlapping(n, haystack) {
+                    is_runic_letter[i..i + n.len()].fill(true);
+                }
+            }
+        }
+
+        is_runic_letter.iter().filter(|&&x| x).count()
+    }
+
+    fn part3(input: &Self::Input<'_>) -> impl Display {
+        // It's making us do a word search, wow
+        let grid = Grid::parse(input.haystack);
+        // The word search wraps around horizontally, but not vertically
+        let wordsearch = grid.wrapping(Wrap::Horizontal);
+
+        let mut is_runic = HashSet::new();
+
+        for &needle in input.needles.iter() {
+            for coords in grid.positions() {
+                // Check the first character before trying to step
+                if needle.chars().next() != wordsearch.get(coords).copied() {
+                    continue;
+                }
+
+                for dir in OrthoDir::ALL {
+                    let (word, word_coords) = get_word(wordsearch, coords, dir, needle.len());
+
+                    if needle == word {
+                        is_runic.extend(word_coords);
+                    }
+                }
+            }
+        }
+
+        is_runic.len()
+    }
+}
+
+utils::quest!(2, Quest2);
@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use utils::{
+    grid::{DiagDir, Direction, Grid, OrthoDir, Point2},
+    Solution,
+};
+
+// Each tile can be dug out to a depth one more than the shallowest of its
+// neighbours, so the total dug out is just the sum of each tile's distance to
+// the nearest undiggable tile.
+fn dig_out<D>(ground: &Grid<char>, adjacent: &[D]) -> usize
+where
+    D: Direction<Delta = Point2>,
+{
+    ground
+        .distance_transform(adjacent, |&c| c != '#')
+        .iter()
+        .map(|(_, &depth)| depth)
+        .sum()
+}
+
+pub struct Quest3;
+
+impl Solution for Quest3 {
+    type Input<'a> = Grid<char>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Grid::parse(input)
+    }
+
+    fn part1(ground: &Self::Input<'_>) -> impl Display {
+        dig_out(ground, &OrthoDir::ALL)
+    }
+
+    fn part2(ground: &Self::Input<'_>) -> impl Display {
+        Self::part1(ground)
+    }
+
+    fn part3(ground: &Self::Input<'_>) -> impl Display {
+        dig_out(ground, &DiagDir::ALL)
+    }
+}
+
+utils::quest!(3, Quest3);
@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use utils::Solution;
+
+fn minimum_strikes_part1(nail_heights: &[i64]) -> i64 {
+    let min = nail_heights.iter().min().copied().unwrap_or(0);
+    nail_heights.iter().sum::<i64>() - min * nail_heights.len() as i64
+}
+
+fn median(values: &[i64]) -> i64 {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+
+    let middle_idx = sorted.len() / 2;
+    sorted[middle_idx]
+}
+
+fn strikes_to_level(nail_heights: &[i64], height: i64) -> i64 {
+    nail_heights.iter().map(|x| x.abs_diff(height) as i64).sum()
+}
+
+fn minimum_strikes_part3(nail_heights: &[i64]) -> i64 {
+    strikes_to_level(nail_heights, median(nail_heights))
+}
+
+pub struct Quest4;
+
+impl Solution for Quest4 {
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.trim().lines().map(|i| i.parse().unwrap()).collect()
+    }
+
+    fn part1(nail_heights: &Self::Input<'_>) -> impl Display {
+        minimum_strikes_part1(nail_heights)
+    }
+
+    fn part2(nail_heights: &Self::Input<'_>) -> impl Display {
+        minimum_strikes_part1(nail_heights)
+    }
+
+    fn part3(nail_heights: &Self::Input<'_>) -> impl Display {
+        minimum_strikes_part3(nail_heights)
+    }
+}
+
+utils::quest!(4, Quest4);
+
+#[cfg(test)]
+mod tests {
+    use utils::bisect::{golden_section_search, ternary_search, Tolerance};
+
+    use super::*;
+
+    // The total number of strikes is convex in the target height, so searching
+    // for its minimum should agree with levelling to the median.
+    const NAIL_HEIGHTS: &[&[i64]] = &[
+        &[2, 4, 5, 6, 8],
+        &[3, 4, 7, 8],
+        &[10, 10, 10],
+        &[1, 100, 101, 102, 5000, 5001],
+        &[-7, 3, 12, 12, 40, 41, 900],
+    ];
+
+    #[test]
+    fn ternary_search_matches_median() {
+        for &nail_heights in NAIL_HEIGHTS {
+            let lowest = *nail_heights.iter().min().unwrap();
+            let highest = *nail_heights.iter().max().unwrap();
+
+            let (height, strikes) = ternary_search(lowest, highest + 1, |height| {
+                strikes_to_level(nail_heights, height)
+            });
+
+            assert_eq!(strikes, minimum_strikes_part3(nail_heights));
+            assert_eq!(strikes, strikes_to_level(nail_heights, height));
+        }
+    }
+
+    #[test]
+    fn golden_section_search_matches_median() {
+        for &nail_heights in NAIL_HEIGHTS {
+            let lowest = *nail_heights.iter().min().unwrap() as f64;
+            let highest = *nail_heights.iter().max().unwrap() as f64;
+
+            // The same cost, but allowing nails to be levelled to any height.
+            let (_, strikes) =
+                golden_section_search(lowest, highest, Tolerance::default(), |height| {
+                    nail_heights
+                        .iter()
+                        .map(|&x| (x as f64 - height).abs())
+                        .sum()
+                });
+
+            let expected = minimum_strikes_part3(nail_heights) as f64;
+            assert!((strikes - expected).abs() < 1e-6, "{strikes} != {expected}");
+        }
+    }
+}
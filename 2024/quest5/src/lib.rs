@@ -0,0 +1,128 @@
+use std::fmt::Display;
+
+use utils::{counter::Counter, cycle_detection::brent_fold, Solution};
+
+fn dance_round(columns: &mut [Vec<i64>], clapper_col: usize) {
+    let absorption_col = (clapper_col + 1) % columns.len();
+
+    let clapper = columns[clapper_col].remove(0);
+
+    let n = ((clapper - 1) as usize) % (2 * columns[absorption_col].len());
+    let insert_pos = n.min(2 * columns[absorption_col].len() - n);
+    columns[absorption_col].insert(insert_pos, clapper);
+}
+
+fn read_column_front(columns: &[Vec<i64>]) -> i64 {
+    columns
+        .iter()
+        .map(|col| col.first().unwrap().to_string())
+        .collect::<Vec<_>>()
+        .join("")
+        .parse()
+        .unwrap()
+}
+
+fn transpose(rows: &[Vec<i64>]) -> Vec<Vec<i64>> {
+    let mut columns = Vec::new();
+
+    for i in 0..rows[0].len() {
+        let mut col = Vec::new();
+        for r in rows {
+            col.push(r[i]);
+        }
+        columns.push(col);
+    }
+
+    columns
+}
+
+fn read_columns(input: &str) -> Vec<Vec<i64>> {
+    let rows: Vec<Vec<i64>> = input
+        .trim()
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|i| i.parse::<i64>().unwrap())
+                .collect()
+        })
+        .collect();
+
+    transpose(&rows)
+}
+
+pub struct Quest5;
+
+impl Solution for Quest5 {
+    type Input<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        read_columns(input)
+    }
+
+    fn part1(columns: &Self::Input<'_>) -> impl Display {
+        let mut columns = columns.clone();
+        let num_cols = columns.len();
+
+        for i in 0..10 {
+            dance_round(&mut columns, i % num_cols);
+        }
+
+        read_column_front(&columns)
+    }
+
+    fn part2(columns: &Self::Input<'_>) -> impl Display {
+        let mut seen_times = Counter::new();
+
+        let mut columns = columns.clone();
+        let num_columns = columns.len();
+
+        for i in 0.. {
+            dance_round(&mut columns, i % num_columns);
+            let number = read_column_front(&columns);
+
+            let seen = seen_times.entry(number);
+            *seen += 1;
+            if *seen >= 2024 {
+                return (i + 1) as i64 * number;
+            }
+        }
+
+        unreachable!()
+    }
+
+    fn part3(columns: &Self::Input<'_>) -> impl Display {
+        let num_columns = columns.len();
+        let next_round = |(mut columns, clapper_col): (Vec<Vec<i64>>, usize)| {
+            dance_round(&mut columns, clapper_col);
+            (columns, (clapper_col + 1) % num_columns)
+        };
+
+        // Nothing is shouted before the first round, so the fold starts from
+        // the state after it. Every number shouted is shouted again within
+        // the first cycle of dance rounds from there, so only rounds up to the
+        // end of that need checking.
+        let (_, highest_seen) = brent_fold(
+            next_round((columns.clone(), 0)),
+            next_round,
+            0,
+            |highest_seen, _, (columns, _)| highest_seen.max(read_column_front(columns)),
+        );
+
+        highest_seen
+    }
+}
+
+utils::quest!(5, Quest5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part3_counts_the_initial_arrangement_when_it_repeats() {
+        // The dance cycles straight back to the starting arrangement, so the
+        // highest number is only shouted at the end of the first cycle.
+        let columns = Quest5::parse("5 4 6\n1 2 3");
+        assert_eq!(Quest5::part3(&columns).to_string(), "546");
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use utils::Solution;
+
+pub struct Dag {
+    adjacency: HashMap<String, Vec<String>>,
+}
+
+impl Dag {
+    fn new(input: &str) -> Self {
+        let mut adjacency = HashMap::new();
+
+        for line in input.trim().lines() {
+            let (parent, children) = line.split_once(':').unwrap();
+            let children = children.split(',').map(|s| s.to_string()).collect();
+
+            adjacency.insert(parent.to_string(), children);
+        }
+
+        Self { adjacency }
+    }
+}
+
+fn visit_all_fruits(dag: &Dag) -> BTreeMap<i32, Vec<Vec<&str>>> {
+    fn visitor<'a>(
+        dag: &'a Dag,
+        node: &'a str,
+        path: &mut Vec<&'a str>,
+        depth: i32,
+        visit: &mut impl FnMut(&[&'a str], i32),
+    ) {
+        if path.contains(&node) {
+            return;
+        }
+
+        path.push(node);
+
+        if node == "@" {
+            visit(path, depth);
+        } else {
+            let children = dag
+                .adjacency
+                .get(node)
+                .map(|x| x.as_slice())
+                .unwrap_or_default();
+
+            for child in children {
+                visitor(dag, child, path, depth + 1, visit);
+            }
+        }
+
+        path.pop();
+    }
+
+    let mut fruits_by_depth = BTreeMap::<i32, Vec<Vec<&str>>>::new();
+
+    visitor(dag, "RR", &mut Vec::new(), 0, &mut |path, depth| {
+        let entry = fruits_by_depth.entry(depth);
+        entry.or_default().push(path.to_vec());
+    });
+
+    fruits_by_depth
+}
+
+pub struct Quest6;
+
+impl Solution for Quest6 {
+    type Input<'a> = Dag;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        Dag::new(input)
+    }
+
+    fn part1(tree: &Self::Input<'_>) -> impl Display {
+        let fruits_by_depth = visit_all_fruits(tree);
+
+        for (_, paths) in fruits_by_depth.iter() {
+            if paths.len() == 1 {
+                return paths[0].join("");
+            }
+        }
+
+        panic!("No depth with only one fruit found")
+    }
+
+    fn part2(tree: &Self::Input<'_>) -> impl Display {
+        let fruits_by_depth = visit_all_fruits(tree);
+
+        for (_, paths) in fruits_by_depth.iter() {
+            if paths.len() == 1 {
+                return paths[0]
+                    .iter()
+                    .map(|s| s.chars().next().unwrap())
+                    .collect::<String>();
+            }
+        }
+
+        panic!("No depth with only one fruit found")
+    }
+
+    fn part3(tree: &Self::Input<'_>) -> impl Display {
+        Self::part2(tree)
+    }
+}
+
+utils::quest!(6, Quest6);
@@ -0,0 +1,305 @@
+use std::fmt::Display;
+
+use utils::{
+    grid::{OrthoDir, SparseGrid},
+    Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Inc,
+    Dec,
+    Noop,
+}
+
+impl Action {
+    fn parse(s: &str) -> Self {
+        match s {
+            "+" => Action::Inc,
+            "-" => Action::Dec,
+            "=" | "S" => Action::Noop,
+            _ => unreachable!("Unknown action {s:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Plan {
+    actions: Vec<Action>,
+}
+
+impl Plan {
+    fn parse(input: &str) -> Self {
+        Self {
+            actions: input.split(',').map(Action::parse).collect(),
+        }
+    }
+
+    fn simulate(&self, steps: i64, initial_power: i64) -> i64 {
+        let mut power = initial_power;
+        let mut total = 0;
+
+        for i in 0..steps {
+            match self.actions[i as usize % self.actions.len()] {
+                Action::Inc => power += 1,
+                Action::Dec => power = (power - 1).max(0),
+                Action::Noop => (),
+            };
+
+            total += power
+        }
+
+        total
+    }
+
+    fn all_perms_with_budget(
+        inc_budget: usize,
+        dec_budget: usize,
+        noop_budget: usize,
+        mut visitor: impl FnMut(Self),
+    ) {
+        fn inner(
+            plan_so_far: &mut Vec<Action>,
+            inc_budget: usize,
+            dec_budget: usize,
+            noop_budget: usize,
+            visitor: &mut impl FnMut(Plan),
+        ) {
+            if (inc_budget, dec_budget, noop_budget) == (0, 0, 0) {
+                return visitor(Plan {
+                    actions: plan_so_far.clone(),
+                });
+            }
+
+            if inc_budget > 0 {
+                plan_so_far.push(Action::Inc);
+                inner(
+                    plan_so_far,
+                    inc_budget - 1,
+                    dec_budget,
+                    noop_budget,
+                    visitor,
+                );
+                plan_so_far.pop();
+            }
+
+            if dec_budget > 0 {
+                plan_so_far.push(Action::Dec);
+                inner(
+                    plan_so_far,
+                    inc_budget,
+                    dec_budget - 1,
+                    noop_budget,
+                    visitor,
+                );
+                plan_so_far.pop();
+            }
+
+            if noop_budget > 0 {
+                plan_so_far.push(Action::Noop);
+                inner(
+                    plan_so_far,
+                    inc_budget,
+                    dec_budget,
+                    noop_budget - 1,
+                    visitor,
+                );
+                plan_so_far.pop();
+            }
+        }
+
+        inner(
+            &mut Vec::new(),
+            inc_budget,
+            dec_budget,
+            noop_budget,
+            &mut visitor,
+        );
+    }
+}
+
+struct Track {
+    terrain: Vec<Action>,
+}
+
+impl Track {
+    fn parse(input: &str) -> Self {
+        let grid = SparseGrid::parse(input.trim(), ' ');
+        assert_eq!(grid.get((0, 0)), Some(&'S'));
+
+        let track = grid
+            .trace((0, 0), OrthoDir::East)
+            .and_then(|trace| trace.into_loop())
+            .unwrap_or_else(|e| panic!("track isn't a single loop: {e}"));
+
+        // The start is the last piece of track reached on each lap.
+        let mut terrain: Vec<_> = track
+            .cells
+            .iter()
+            .map(|(_, &c)| Action::parse(&c.to_string()))
+            .collect();
+        terrain.rotate_left(1);
+
+        Self { terrain }
+    }
+
+    fn get_and_step(
+        &self,
+        plan: &Plan,
+        indices: (usize, usize),
+        power: i64,
+    ) -> ((usize, usize), i64) {
+        let (terrain_idx, plan_idx) = indices;
+
+        let new_power = match (self.terrain[terrain_idx], plan.actions[plan_idx]) {
+            (Action::Inc, _) => power + 1,
+            (Action::Dec, _) => (power - 1).max(0),
+            (Action::Noop, Action::Inc) => power + 1,
+            (Action::Noop, Action::Dec) => (power - 1).max(0),
+            (Action::Noop, Action::Noop) => power,
+        };
+
+        let next_terrain_idx = (terrain_idx + 1) % self.terrain.len();
+        let next_plan_idx = (plan_idx + 1) % plan.actions.len();
+
+        ((next_terrain_idx, next_plan_idx), new_power)
+    }
+
+    fn simulate(&self, plan: &Plan, rounds: usize, initial_power: i64) -> i64 {
+        let mut total = 0;
+        let mut power = initial_power;
+
+        let mut indices = (0, 0);
+
+        for _ in 0..rounds {
+            for _ in 0..self.terrain.len() {
+                (indices, power) = self.get_and_step(plan, indices, power);
+                total += power;
+            }
+        }
+
+        total
+    }
+}
+
+fn parse_plans(input: &str) -> Vec<(&str, Plan)> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (name, plan) = line.split_once(':').unwrap();
+            (name, Plan::parse(plan))
+        })
+        .collect()
+}
+
+const PART2_TRACK: &str = "
+S-=++=-==++=++=-=+=-=+=+=--=-=++=-==++=-+=-=+=-=+=+=++=-+==++=++=-=-=--
+-                                                                     -
+=                                                                     =
++                                                                     +
+=                                                                     +
++                                                                     =
+=                                                                     =
+-                                                                     -
+--==++++==+=+++-=+=-=+=-+-=+-=+-=+=-=+=--=+++=++=+++==++==--=+=++==+++-
+";
+
+const PART3_TRACK: &str = "
+S+= +=-== +=++=     =+=+=--=    =-= ++=     +=-  =+=++=-+==+ =++=-=-=--
+- + +   + =   =     =      =   == = - -     - =  =         =-=        -
+= + + +-- =-= ==-==-= --++ +  == == = +     - =  =    ==++=    =++=-=++
++ + + =     +         =  + + == == ++ =     = =  ==   =   = =++=
+= = + + +== +==     =++ == =+=  =  +  +==-=++ =   =++ --= + =
++ ==- = + =   = =+= =   =       ++--          +     =   = = =--= ==++==
+=     ==- ==+-- = = = ++= +=--      ==+ ==--= +--+=-= ==- ==   =+=    =
+-               = = = =   +  +  ==+ = = +   =        ++    =          -
+-               = + + =   +  -  = + = = +   =        +     =          -
+--==++++==+=+++-= =-= =-+-=  =+-= =-= =--   +=++=+++==     -=+=++==+++-
+";
+
+fn ranking(plans: &[(&str, Plan)], score: impl Fn(&Plan) -> i64) -> String {
+    let mut plans = plans.to_vec();
+    plans.sort_by_cached_key(|(_name, plan)| -score(plan));
+
+    plans
+        .iter()
+        .map(|(name, _plan)| *name)
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+// Ranks the plans by how much power they've gathered after 10 laps of the
+// given track.
+fn race_ranking(plans: &[(&str, Plan)], track: &str) -> String {
+    let track = Track::parse(track);
+    ranking(plans, |plan| track.simulate(plan, 10, 10))
+}
+
+pub struct Quest7;
+
+impl Solution for Quest7 {
+    type Input<'a> = Vec<(&'a str, Plan)>;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        parse_plans(input)
+    }
+
+    fn part1(plans: &Self::Input<'_>) -> impl Display {
+        ranking(plans, |plan| plan.simulate(10, 10))
+    }
+
+    fn part2(plans: &Self::Input<'_>) -> impl Display {
+        race_ranking(plans, PART2_TRACK)
+    }
+
+    fn part3(plans: &Self::Input<'_>) -> impl Display {
+        let (_name, rival_plan) = &plans[0];
+        let track = Track::parse(PART3_TRACK);
+
+        let score_to_beat = track.simulate(rival_plan, 2024, 10);
+
+        let mut winning_plans = 0;
+        // This is slow and could be sped up with some form of cycle detection, but
+        // geez I don't want to think about doing the maths, especially with the
+        // condition that the power can't drop below 0!
+        Plan::all_perms_with_budget(5, 3, 3, |plan| {
+            let score = track.simulate(&plan, 2024, 10);
+            if score > score_to_beat {
+                winning_plans += 1;
+            }
+        });
+
+        winning_plans
+    }
+}
+
+utils::quest!(
+    7,
+    Quest7,
+    ignore {
+        part2: "the example races on its own track; see tests::part2_track",
+        part3: "the puzzle gives no example for this part",
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_track() {
+        // Each plan's choice only matters on the track's = and S segments.
+        // Every step of A's race does at least as well as C's, which does at
+        // least as well as B's, even though B beats C without a track.
+        let plans = parse_plans("B:=,=,=,=\nC:-,+,-,+\nA:+,+,+,+");
+        let track = "
+S+=
+- -
+===
+";
+
+        assert_eq!(Quest7::part1(&plans).to_string(), "ABC");
+        assert_eq!(race_ranking(&plans, track), "ACB");
+    }
+}
@@ -0,0 +1,91 @@
+use std::ops::RangeInclusive;
+
+use utils::{
+    input::{Args, USAGE},
+    solution::exit_with_usage,
+    Quest,
+};
+
+const QUESTS: &[Quest] = &[
+    quest1::QUEST,
+    quest2::QUEST,
+    quest3::QUEST,
+    quest4::QUEST,
+    quest5::QUEST,
+    quest6::QUEST,
+    quest7::QUEST,
+    quest8::QUEST,
+    quest9::QUEST,
+    quest10::QUEST,
+    quest11::QUEST,
+];
+
+// Accepts `all`, a single quest number, or a range such as `3..=6` or `3..7`.
+fn parse_selection(s: &str) -> Option<RangeInclusive<u32>> {
+    if s == "all" {
+        return Some(1..=u32::MAX);
+    }
+
+    if let Some((start, end)) = s.split_once("..=") {
+        return Some(start.parse().ok()?..=end.parse().ok()?);
+    }
+
+    if let Some((start, end)) = s.split_once("..") {
+        let end: u32 = end.parse().ok()?;
+        return Some(start.parse().ok()?..=end.checked_sub(1)?);
+    }
+
+    let quest = s.parse().ok()?;
+    Some(quest..=quest)
+}
+
+fn main() {
+    let usage = format!("runner <N | A..B | A..=B | all> {USAGE}");
+    let mut args = std::env::args().skip(1);
+
+    let Some(selection) = args.next() else {
+        exit_with_usage("no quests selected", &usage);
+    };
+    let Some(range) = parse_selection(&selection) else {
+        exit_with_usage(format!("invalid quest selection `{selection}`"), &usage);
+    };
+    let args = Args::parse(args).unwrap_or_else(|e| exit_with_usage(e, &usage));
+
+    let quests: Vec<_> = QUESTS
+        .iter()
+        .filter(|quest| range.contains(&quest.number))
+        .collect();
+
+    if quests.is_empty() {
+        exit_with_usage(format!("no quests match `{selection}`"), &usage);
+    }
+    if quests.len() > 1 && args.inputs.iter().any(Option::is_some) {
+        exit_with_usage(
+            "per-part input files can only be given when running a single quest",
+            &usage,
+        );
+    }
+
+    let mut mismatches = 0;
+    for quest in quests {
+        println!("== Quest {} ==", quest.number);
+
+        // With the runner, --dir points at a tree laid out like this
+        // workspace, with each quest's inputs in its own subdirectory.
+        let mut quest_args = args.clone();
+        quest_args.dir = args
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(format!("quest{}", quest.number)));
+
+        match quest.run(&quest_args) {
+            Ok(n) => mismatches += n,
+            Err(e) => exit_with_usage(e, &usage),
+        }
+    }
+
+    if mismatches > 0 {
+        eprintln!("{mismatches} answer(s) did not match the recorded answers");
+        std::process::exit(1);
+    }
+}
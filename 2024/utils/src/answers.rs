@@ -0,0 +1,85 @@
+use std::{io, path::Path};
+
+use crate::input::InputError;
+
+// Known answers for a quest, kept in a file alongside its inputs with one
+// `partN: answer` line per solved part. Parts without a line are unchecked.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers([Option<String>; 3]);
+
+impl Answers {
+    pub fn parse(s: &str, path: &Path) -> Result<Self, InputError> {
+        let mut answers = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let malformed = || InputError::MalformedAnswers {
+                path: path.to_path_buf(),
+                line: i + 1,
+            };
+
+            let (part, answer) = line.split_once(':').ok_or_else(malformed)?;
+            let part = match part.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                "part3" => 3,
+                _ => return Err(malformed()),
+            };
+
+            answers.0[part - 1] = Some(answer.trim().to_string());
+        }
+
+        Ok(answers)
+    }
+
+    // Loads answers from the given file. A missing file just means nothing
+    // has been recorded yet.
+    pub fn load(path: &Path) -> Result<Self, InputError> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s, path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(InputError::UnreadableAnswers {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.0[part - 1].as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let path = Path::new("answers.txt");
+
+        let answers = Answers::parse("\npart3:  abc def \n\n   \npart1:42\n", path).unwrap();
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), None);
+        assert_eq!(answers.get(3), Some("abc def"));
+
+        assert_eq!(Answers::parse("", path).unwrap(), Answers::default());
+
+        for (input, bad_line) in [
+            ("part1: 1\npart2 2\n", 2),
+            ("part4: 1\n", 1),
+            ("\n\n1: 2\n", 3),
+        ] {
+            assert!(
+                matches!(
+                    Answers::parse(input, path),
+                    Err(InputError::MalformedAnswers { line, .. }) if line == bad_line
+                ),
+                "{input:?}"
+            );
+        }
+    }
+}
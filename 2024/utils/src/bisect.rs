@@ -1,12 +1,56 @@
 // More general purpose binary search algorithms than just operating on containers.
 
-// Returns the first element for which the predicate returns false.
-pub fn binary_search(mut start: u64, mut end: u64, mut predicate: impl FnMut(u64) -> bool) -> u64 {
+use std::fmt;
+
+// The integer operations the searches need, implemented for all the primitive
+// integer types.
+pub trait Integer: Copy + Ord {
+    const ONE: Self;
+    const MAX: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    // The midpoint of self and other, rounded down, without overflowing.
+    fn midpoint_floor(self, other: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ONE: Self = 1;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn midpoint_floor(self, other: Self) -> Self {
+                    // The shared bits, plus half the differing ones. The shift
+                    // is arithmetic for signed types, so this rounds down.
+                    (self & other) + ((self ^ other) >> 1)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// Returns the first element in start..end for which the predicate returns
+// false, or end if there isn't one. Like slice::partition_point, the predicate
+// must be true for some prefix of the range and false for the rest.
+pub fn binary_search<T: Integer>(
+    mut start: T,
+    mut end: T,
+    mut predicate: impl FnMut(T) -> bool,
+) -> T {
     while end > start {
-        let mid = start + (end - start) / 2;
+        let mid = start.midpoint_floor(end);
 
         if predicate(mid) {
-            start = mid + 1;
+            // mid < end, so this can't overflow.
+            start = mid.checked_add(T::ONE).unwrap();
         } else {
             end = mid;
         }
@@ -15,15 +59,391 @@ pub fn binary_search(mut start: u64, mut end: u64, mut predicate: impl FnMut(u64
     start
 }
 
-pub fn exponential_search(start: u64, mut predicate: impl FnMut(u64) -> bool) -> u64 {
-    let mut step = 1;
+// The first element in start..end for which f is at least target, where f
+// never decreases.
+pub fn lower_bound<T: Integer, V: Ord>(
+    start: T,
+    end: T,
+    target: &V,
+    mut f: impl FnMut(T) -> V,
+) -> T {
+    binary_search(start, end, |x| f(x) < *target)
+}
+
+// The first element in start..end for which f is greater than target, where f
+// never decreases.
+pub fn upper_bound<T: Integer, V: Ord>(
+    start: T,
+    end: T,
+    target: &V,
+    mut f: impl FnMut(T) -> V,
+) -> T {
+    binary_search(start, end, |x| f(x) <= *target)
+}
+
+// Like binary_search, but for when there's no known end to the range: the
+// range is instead found by doubling steps away from start. The search stops
+// at limit (inclusive) if one is given, or at the type's maximum otherwise,
+// and None is returned if the predicate is still true there.
+pub fn exponential_search<T: Integer>(
+    start: T,
+    limit: Option<T>,
+    mut predicate: impl FnMut(T) -> bool,
+) -> Option<T> {
+    let limit = limit.unwrap_or(T::MAX);
+    assert!(
+        start <= limit,
+        "exponential search must start within its limit"
+    );
+
+    let mut lower = start;
+    // Once the step overflows, the only place left to look is the limit.
+    let mut step = Some(T::ONE);
     loop {
-        if !predicate(start + step) {
+        let probe = step
+            .and_then(|step| start.checked_add(step))
+            .filter(|&probe| probe < limit)
+            .unwrap_or(limit);
+
+        if !predicate(probe) {
+            return Some(binary_search(lower, probe, predicate));
+        }
+        if probe == limit {
+            return None;
+        }
+
+        // probe < limit, so this can't overflow.
+        lower = probe.checked_add(T::ONE).unwrap();
+        step = step.and_then(|step| step.checked_add(step));
+    }
+}
+
+// Finds the minimum of f over start..end, which must not be empty, returning
+// where it is and its value. f must be unimodal: strictly decreasing up to its
+// minimum, then never decreasing after it. Rather than comparing two interior
+// points, this binary searches for where f stops going down, which takes
+// fewer evaluations and copes with a flat minimum.
+pub fn ternary_search<T: Integer, V: Ord>(start: T, end: T, mut f: impl FnMut(T) -> V) -> (T, V) {
+    assert!(start < end, "can't search an empty range for a minimum");
+
+    let argmin = binary_search(start, end, |x| match x.checked_add(T::ONE) {
+        Some(next) if next < end => f(x) > f(next),
+        _ => false,
+    });
+
+    (argmin, f(argmin))
+}
+
+// When to stop narrowing down a floating point search: once the bracket is no
+// wider than absolute, or than relative times the size of the estimate,
+// whichever is larger. Searches give up after max_iterations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+    pub max_iterations: u32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 1e-12,
+            relative: 1e-12,
+            max_iterations: 200,
+        }
+    }
+}
+
+// Finds the minimum of a unimodal function f between lo and hi, narrowing the
+// bracket until it's within tolerance, and returns where the minimum is and
+// its value. If the iterations run out, or the bracket stops shrinking because
+// it's down to the precision of an f64, the best estimate so far is returned.
+pub fn golden_section_search(
+    mut lo: f64,
+    mut hi: f64,
+    tolerance: Tolerance,
+    mut f: impl FnMut(f64) -> f64,
+) -> (f64, f64) {
+    // 1 / phi, so that each narrowing keeps one of the two interior points.
+    let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+
+    let mut left = hi - inv_phi * (hi - lo);
+    let mut right = lo + inv_phi * (hi - lo);
+    let mut f_left = f(left);
+    let mut f_right = f(right);
+
+    for _ in 0..tolerance.max_iterations {
+        let width = hi - lo;
+        let mid = lo + width / 2.0;
+        if width <= tolerance.absolute.max(tolerance.relative * mid.abs()) {
             break;
         }
 
-        step *= 2;
+        if f_left < f_right {
+            hi = right;
+            right = left;
+            f_right = f_left;
+            left = hi - inv_phi * (hi - lo);
+            f_left = f(left);
+        } else {
+            lo = left;
+            left = right;
+            f_left = f_right;
+            right = lo + inv_phi * (hi - lo);
+            f_right = f(right);
+        }
+
+        if hi - lo >= width {
+            break;
+        }
+    }
+
+    let x = lo + (hi - lo) / 2.0;
+    (x, f(x))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RootError {
+    // f has the same sign at both ends of the bracket, so it needn't have a
+    // root between them.
+    NotBracketed,
+    // The bracket was still too wide after the maximum number of iterations,
+    // with this as the best estimate so far.
+    TooManyIterations(f64),
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootError::NotBracketed => write!(f, "root is not bracketed"),
+            RootError::TooManyIterations(estimate) => {
+                write!(f, "root did not converge (best estimate {estimate})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RootError {}
+
+// Finds a root of the continuous function f between lo and hi, where f(lo)
+// and f(hi) must have opposite signs (or either be zero).
+pub fn bisect_root(
+    mut lo: f64,
+    mut hi: f64,
+    tolerance: Tolerance,
+    mut f: impl FnMut(f64) -> f64,
+) -> Result<f64, RootError> {
+    let f_lo = f(lo);
+    let f_hi = f(hi);
+
+    if f_lo == 0.0 {
+        return Ok(lo);
+    }
+    if f_hi == 0.0 {
+        return Ok(hi);
+    }
+    if f_lo.signum() == f_hi.signum() {
+        return Err(RootError::NotBracketed);
+    }
+
+    let lo_is_negative = f_lo < 0.0;
+    for _ in 0..tolerance.max_iterations {
+        let mid = lo + (hi - lo) / 2.0;
+        let width = (hi - lo).abs();
+        if width <= tolerance.absolute.max(tolerance.relative * mid.abs()) {
+            return Ok(mid);
+        }
+
+        let f_mid = f(mid);
+        if f_mid == 0.0 {
+            return Ok(mid);
+        }
+
+        if (f_mid < 0.0) == lo_is_negative {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Err(RootError::TooManyIterations(lo + (hi - lo) / 2.0))
+}
+
+// The k-th root of n rounded down, computed exactly rather than through
+// floating point, which can land either side of a whole number.
+pub fn integer_root(n: u64, k: u32) -> u64 {
+    assert!(k > 0, "there's no zeroth root");
+
+    match (n, k) {
+        (0 | 1, _) | (_, 1) => n,
+        (_, 2) => n.isqrt(),
+        // For n >= 2 and k >= 2, the root is always below n.
+        _ => binary_search(1, n, |x| x.checked_pow(k).is_some_and(|p| p <= n)) - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_search_near_the_limits() {
+        assert_eq!(binary_search(u8::MAX - 10, u8::MAX, |x| x < 250), 250);
+        assert_eq!(binary_search(u64::MAX - 5, u64::MAX, |_| true), u64::MAX);
+        assert_eq!(binary_search(i64::MIN, i64::MAX, |x| x < 0), 0);
+        assert_eq!(binary_search(i8::MIN, i8::MAX, |x| x < -100), -100);
+        assert_eq!(binary_search(5u32, 5, |_| true), 5);
+    }
+
+    #[test]
+    fn bounds_with_duplicates() {
+        let values = [1, 3, 3, 3, 5, 8];
+        let at = |i: usize| values[i];
+
+        for (target, lower, upper) in [
+            (0, 0, 0),
+            (1, 0, 1),
+            (3, 1, 4),
+            (4, 4, 4),
+            (8, 5, 6),
+            (9, 6, 6),
+        ] {
+            assert_eq!(lower_bound(0, values.len(), &target, at), lower, "{target}");
+            assert_eq!(upper_bound(0, values.len(), &target, at), upper, "{target}");
+        }
+    }
+
+    #[test]
+    fn exponential_search_limits() {
+        // Still true at the type's maximum, or the given limit.
+        assert_eq!(exponential_search(0u8, None, |_| true), None);
+        assert_eq!(exponential_search(0i64, None, |_| true), None);
+        assert_eq!(exponential_search(3u32, Some(10), |x| x < 100), None);
+
+        assert_eq!(
+            exponential_search(0u8, None, |x| x < u8::MAX),
+            Some(u8::MAX)
+        );
+        assert_eq!(exponential_search(3u32, Some(10), |x| x < 10), Some(10));
+        assert_eq!(exponential_search(3u32, Some(10), |x| x < 5), Some(5));
+        assert_eq!(exponential_search(3u32, Some(3), |_| false), Some(3));
+        assert_eq!(
+            exponential_search(1u64 << 40, None, |x| x < 1 << 62),
+            Some(1 << 62)
+        );
     }
 
-    binary_search(start + step / 2, start + step, predicate)
+    #[test]
+    fn exponential_search_from_negative_starts() {
+        assert_eq!(exponential_search(-50i32, None, |x| x < -7), Some(-7));
+        assert_eq!(exponential_search(-50i32, None, |x| x < 1000), Some(1000));
+        assert_eq!(exponential_search(i8::MIN, None, |x| x < 100), Some(100));
+        assert_eq!(exponential_search(i8::MIN, Some(-1), |_| true), None);
+        assert_eq!(exponential_search(i64::MIN, None, |x| x < 0), Some(0));
+    }
+
+    #[test]
+    fn golden_section_search_stops_at_f64_precision() {
+        let f = |x: f64| (x - 1e6 - 3.0).powi(2);
+
+        // Far narrower than the spacing of f64s around 1e6.
+        let tight = Tolerance {
+            absolute: 1e-12,
+            relative: 0.0,
+            ..Tolerance::default()
+        };
+        let (x, _) = golden_section_search(1e6, 1e6 + 10.0, tight, f);
+        assert!((x - 1e6 - 3.0).abs() < 1e-6, "{x}");
+
+        let exact = Tolerance {
+            absolute: 0.0,
+            relative: 0.0,
+            max_iterations: u32::MAX,
+        };
+        let (x, _) = golden_section_search(0.0, 10.0, exact, |x| (x - 3.0).powi(2));
+        assert!((x - 3.0).abs() < 1e-6, "{x}");
+    }
+
+    #[test]
+    fn integer_roots_near_whole_numbers() {
+        for k in [3u64, 1 << 20, 3_037_000_499, u32::MAX as u64] {
+            let square = k * k;
+            assert_eq!(integer_root(square - 1, 2), k - 1);
+            assert_eq!(integer_root(square, 2), k);
+            assert_eq!(integer_root(square + 1, 2), k);
+        }
+        assert_eq!(integer_root(u64::MAX, 2), u32::MAX as u64);
+
+        for k in [2u64, 1_000, 2_642_245] {
+            let cube = k.pow(3);
+            assert_eq!(integer_root(cube - 1, 3), k - 1);
+            assert_eq!(integer_root(cube, 3), k);
+            assert_eq!(integer_root(cube + 1, 3), k);
+        }
+    }
+
+    #[test]
+    fn integer_roots_where_powers_overflow() {
+        // 2642246 cubed is just past u64::MAX.
+        assert_eq!(integer_root(u64::MAX, 3), 2_642_245);
+        assert_eq!(integer_root(u64::MAX, 40), 3);
+        assert_eq!(integer_root(u64::MAX, 63), 2);
+        assert_eq!(integer_root(u64::MAX, 64), 1);
+        assert_eq!(integer_root(u64::MAX, 1000), 1);
+        assert_eq!(integer_root(1 << 63, 63), 2);
+        assert_eq!(integer_root((1 << 63) - 1, 63), 1);
+
+        assert_eq!(integer_root(0, 5), 0);
+        assert_eq!(integer_root(1, 5), 1);
+        assert_eq!(integer_root(12345, 1), 12345);
+    }
+
+    #[test]
+    #[should_panic(expected = "no zeroth root")]
+    fn integer_root_zero() {
+        integer_root(8, 0);
+    }
+
+    #[test]
+    fn bisect_roots() {
+        let root = bisect_root(0.0, 2.0, Tolerance::default(), |x| x * x - 2.0).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-9, "{root}");
+
+        // Either way round.
+        let root = bisect_root(4.0, 1.0, Tolerance::default(), |x| 3.0 - x).unwrap();
+        assert!((root - 3.0).abs() < 1e-9, "{root}");
+
+        assert_eq!(
+            bisect_root(-1.0, 1.0, Tolerance::default(), |x| x * x + 1.0),
+            Err(RootError::NotBracketed)
+        );
+    }
+
+    #[test]
+    fn bisect_root_at_an_endpoint() {
+        assert_eq!(bisect_root(0.0, 5.0, Tolerance::default(), |x| x), Ok(0.0));
+        assert_eq!(bisect_root(-3.0, 0.0, Tolerance::default(), |x| x), Ok(0.0));
+        // Zero at one end counts as bracketed even if the other end has the
+        // same sign as everything else.
+        assert_eq!(
+            bisect_root(2.0, 5.0, Tolerance::default(), |x| x - 2.0),
+            Ok(2.0)
+        );
+    }
+
+    #[test]
+    fn bisect_root_gives_up() {
+        let tolerance = Tolerance {
+            max_iterations: 1,
+            ..Tolerance::default()
+        };
+
+        match bisect_root(0.0, 2.0, tolerance, |x| x * x - 2.0) {
+            Err(error @ RootError::TooManyIterations(estimate)) => {
+                assert!((1.0..=2.0).contains(&estimate), "{estimate}");
+                assert!(error.to_string().contains("did not converge"));
+            }
+            result => panic!("expected to run out of iterations, got {result:?}"),
+        }
+    }
 }
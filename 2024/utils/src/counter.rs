@@ -0,0 +1,241 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, AddAssign, Index, Mul, Sub, SubAssign},
+};
+
+// A multiset, counting how many of each key it holds. Keys that have never been
+// counted have a count of 0. Counts can be scaled or subtracted, so can go
+// negative. A key whose count comes back to 0 is treated as absent: it isn't
+// iterated over, counted by len, or compared by ==.
+#[derive(Debug, Clone)]
+pub struct Counter<K: Hash + Eq>(HashMap<K, i64>);
+
+impl<K: Hash + Eq> Default for Counter<K> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> i64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0.get(key).copied().unwrap_or(0)
+    }
+
+    pub fn entry(&mut self, key: K) -> &mut i64 {
+        self.0.entry(key).or_default()
+    }
+
+    // The number of distinct keys with a non-zero count.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, i64)> {
+        self.0
+            .iter()
+            .filter(|(_, &count)| count != 0)
+            .map(|(key, &count)| (key, count))
+    }
+
+    // Drops the keys whose counts have come back to 0, which entry can leave
+    // behind.
+    fn prune(&mut self) {
+        self.0.retain(|_, count| *count != 0);
+    }
+
+    // The sum of all the counts.
+    pub fn total(&self) -> i64 {
+        self.iter().map(|(_, count)| count).sum()
+    }
+
+    // Every key with its count, from most to least common. Keys with equal
+    // counts are in no particular order.
+    pub fn most_common(&self) -> Vec<(&K, i64)> {
+        let mut counts: Vec<_> = self.iter().collect();
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+}
+
+impl<K, Q> Index<&Q> for Counter<K>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+{
+    type Output = i64;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.0.get(key).unwrap_or(&0)
+    }
+}
+
+impl<K: Hash + Eq> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, count)| other.get(key) == count)
+    }
+}
+
+impl<K: Hash + Eq> Eq for Counter<K> {}
+
+impl<K: Hash + Eq> IntoIterator for Counter<K> {
+    type Item = (K, i64);
+    type IntoIter = std::collections::hash_map::IntoIter<K, i64>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.prune();
+        self.0.into_iter()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::default();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            *self.entry(key) += 1;
+        }
+    }
+}
+
+impl<K: Hash + Eq> AddAssign for Counter<K> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (key, count) in rhs {
+            *self.entry(key) += count;
+        }
+        self.prune();
+    }
+}
+
+impl<K: Hash + Eq> Add for Counter<K> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<K: Hash + Eq> SubAssign for Counter<K> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (key, count) in rhs {
+            *self.entry(key) -= count;
+        }
+        self.prune();
+    }
+}
+
+impl<K: Hash + Eq> Sub for Counter<K> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K: Hash + Eq> Mul<i64> for Counter<K> {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (k, v * rhs))
+                .filter(|&(_, v)| v != 0)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_counts_are_absent() {
+        let a: Counter<_> = "abracadabra".chars().collect();
+        assert_eq!(a.clone() - a.clone(), Counter::new());
+        assert_eq!(a.clone() * 2 - a.clone(), a);
+
+        let mut b = a.clone();
+        b -= "aaaaa".chars().collect();
+        assert_eq!(b.get(&'a'), 0);
+        assert_eq!(b.len(), 4);
+        assert!(b.iter().all(|(_, count)| count != 0));
+        assert_eq!(b.clone() + "aaaaa".chars().collect(), a);
+
+        let mut c = Counter::new();
+        *c.entry("x") += 1;
+        *c.entry("x") -= 1;
+        *c.entry("y") -= 2;
+        assert_eq!(c.len(), 1);
+        assert_eq!(c.most_common(), [(&"y", -2)]);
+        assert_eq!(c.total(), -2);
+        assert_eq!(c, Counter::new() - ["y", "y"].into_iter().collect());
+        assert!(c.into_iter().eq([("y", -2)]));
+    }
+
+    #[test]
+    fn missing_keys_index_to_zero() {
+        let a: Counter<_> = ["x", "y", "x"].into_iter().collect();
+        assert_eq!(a["x"], 2);
+        assert_eq!(a["z"], 0);
+        assert_eq!(a.get("z"), 0);
+
+        let words: Counter<String> = ["a".to_string()].into_iter().collect();
+        assert_eq!(words["a"], 1);
+        assert_eq!(words["b"], 0);
+    }
+
+    #[test]
+    fn most_common_is_by_descending_count() {
+        let a: Counter<_> = "mississippi".chars().collect();
+        let counts: Vec<_> = a
+            .most_common()
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect();
+        assert_eq!(counts, [4, 4, 2, 1]);
+        assert_eq!(a.most_common()[2], (&'p', 2));
+        assert_eq!(a.most_common()[3], (&'m', 1));
+
+        let mut b = a.clone() - "ssss".chars().collect();
+        *b.entry('q') -= 3;
+        let order: Vec<_> = b.most_common().into_iter().map(|(&key, _)| key).collect();
+        assert_eq!(order, ['i', 'p', 'm', 'q']);
+        assert_eq!(b.total(), 4);
+    }
+
+    #[test]
+    fn multiplying_by_zero_empties() {
+        let a: Counter<_> = "abracadabra".chars().collect();
+        let zero = 0;
+        let empty = a.clone() * zero;
+        assert!(empty.is_empty());
+        assert_eq!(empty.total(), 0);
+        assert_eq!(empty, Counter::new());
+
+        assert_eq!(a.clone() * -1 + a.clone(), Counter::new());
+        assert_eq!((a.clone() * 3).total(), 33);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, hash::Hash, iter::Sum};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CycleInfo<S> {
@@ -12,26 +12,109 @@ pub struct CycleInfo<S> {
 pub fn floyd<S>(initial_element: S, next: impl Fn(S) -> S) -> CycleInfo<S>
 where
     S: Clone + Eq,
+{
+    floyd_by_key(initial_element, next, S::clone)
+}
+
+pub fn brent<S>(initial_element: S, next: impl Fn(S) -> S) -> CycleInfo<S>
+where
+    S: Clone + Eq,
+{
+    brent_by_key(initial_element, next, S::clone)
+}
+
+// Like brent, but also folds over every distinct state, in the same way as
+// hashmap_fold. The states are walked again once the cycle is known, which
+// takes start + length more calls to next, but no states need to be kept
+// around.
+pub fn brent_fold<S, A>(
+    initial_element: S,
+    next: impl Fn(S) -> S,
+    init: A,
+    mut fold: impl FnMut(A, u64, &S) -> A,
+) -> (CycleInfo<S>, A)
+where
+    S: Clone + Eq,
+{
+    let cycle_info = brent(initial_element, &next);
+
+    let mut acc = init;
+    let mut curr = cycle_info.initial_element.clone();
+    for i in 0..cycle_info.start + cycle_info.length {
+        if i > 0 {
+            curr = next(curr);
+        }
+        acc = fold(acc, i, &curr);
+    }
+
+    (cycle_info, acc)
+}
+
+pub fn hashmap<S>(initial_element: S, next: impl Fn(S) -> S) -> CycleInfo<S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen_states = HashMap::new();
+    seen_states.insert(initial_element.clone(), 0);
+
+    let mut curr = initial_element.clone();
+    for curr_cycles in 1.. {
+        curr = next(curr);
+
+        if let Some(prev_cycles) = seen_states.insert(curr.clone(), curr_cycles) {
+            let length = curr_cycles - prev_cycles;
+            return CycleInfo {
+                start: prev_cycles,
+                length,
+
+                initial_element,
+                first_element_in_cycle: curr,
+            };
+        }
+    }
+
+    unreachable!()
+}
+
+// The by_key variants below detect cycles in key(state) rather than the whole
+// state, so that bookkeeping which never repeats (step counters, running
+// totals) can be carried along without breaking detection. They also allow
+// next to keep mutable scratch space between calls, but it must still be
+// deterministic: start is found by stepping from the initial state a second
+// time, and will be wrong if that doesn't retrace the same states. The
+// returned first_element_in_cycle is the full state reached after start
+// iterations.
+
+pub fn floyd_by_key<S, K>(
+    initial_element: S,
+    mut next: impl FnMut(S) -> S,
+    key: impl Fn(&S) -> K,
+) -> CycleInfo<S>
+where
+    S: Clone,
+    K: Eq,
 {
     let mut tortoise = next(initial_element.clone());
     let mut hare = next(tortoise.clone());
 
-    while tortoise != hare {
+    while key(&tortoise) != key(&hare) {
         tortoise = next(tortoise);
-        hare = next(next(hare));
+        hare = next(hare);
+        hare = next(hare);
     }
 
     let mut mu = 0;
     tortoise = initial_element.clone();
-    while tortoise != hare {
+    while key(&tortoise) != key(&hare) {
         tortoise = next(tortoise);
         hare = next(hare);
         mu += 1;
     }
 
     let mut lambda = 1;
+    let tortoise_key = key(&tortoise);
     hare = next(tortoise.clone());
-    while tortoise != hare {
+    while tortoise_key != key(&hare) {
         hare = next(hare);
         lambda += 1;
     }
@@ -45,19 +128,24 @@ where
     }
 }
 
-pub fn brent<S>(initial_element: S, next: impl Fn(S) -> S) -> CycleInfo<S>
+pub fn brent_by_key<S, K>(
+    initial_element: S,
+    mut next: impl FnMut(S) -> S,
+    key: impl Fn(&S) -> K,
+) -> CycleInfo<S>
 where
-    S: Clone + Eq,
+    S: Clone,
+    K: Eq,
 {
     let mut power = 1;
     let mut lambda = 1;
 
-    let mut tortoise = initial_element.clone();
+    let mut tortoise_key = key(&initial_element);
     let mut hare = next(initial_element.clone());
 
-    while tortoise != hare {
+    while tortoise_key != key(&hare) {
         if power == lambda {
-            tortoise = hare.clone();
+            tortoise_key = key(&hare);
             power *= 2;
             lambda = 0;
         }
@@ -66,7 +154,7 @@ where
         lambda += 1;
     }
 
-    tortoise = initial_element.clone();
+    let mut tortoise = initial_element.clone();
     hare = initial_element.clone();
 
     for _ in 0..lambda {
@@ -74,7 +162,7 @@ where
     }
 
     let mut mu = 0;
-    while tortoise != hare {
+    while key(&tortoise) != key(&hare) {
         tortoise = next(tortoise);
         hare = next(hare);
         mu += 1;
@@ -89,10 +177,57 @@ where
     }
 }
 
-pub fn hashmap<S>(initial_element: S, next: impl Fn(S) -> S) -> CycleInfo<S>
+// Only keys are remembered, so once the cycle is found the state at its start
+// is recovered by stepping from the initial state again.
+pub fn hashmap_by_key<S, K>(
+    initial_element: S,
+    mut next: impl FnMut(S) -> S,
+    key: impl Fn(&S) -> K,
+) -> CycleInfo<S>
+where
+    S: Clone,
+    K: Eq + Hash,
+{
+    let mut seen_keys = HashMap::new();
+    seen_keys.insert(key(&initial_element), 0);
+
+    let mut curr = initial_element.clone();
+    for curr_cycles in 1.. {
+        curr = next(curr);
+
+        if let Some(prev_cycles) = seen_keys.insert(key(&curr), curr_cycles) {
+            let mut first_element_in_cycle = initial_element.clone();
+            for _ in 0..prev_cycles {
+                first_element_in_cycle = next(first_element_in_cycle);
+            }
+
+            return CycleInfo {
+                start: prev_cycles,
+                length: curr_cycles - prev_cycles,
+
+                initial_element,
+                first_element_in_cycle,
+            };
+        }
+    }
+
+    unreachable!()
+}
+
+// Like hashmap, but also folds over every distinct state along the way: the
+// states after 0 up to start + length - 1 iterations, which covers the prefix
+// and one full cycle. fold is given each state's iteration number too.
+pub fn hashmap_fold<S, A>(
+    initial_element: S,
+    next: impl Fn(S) -> S,
+    init: A,
+    mut fold: impl FnMut(A, u64, &S) -> A,
+) -> (CycleInfo<S>, A)
 where
     S: Clone + Eq + Hash,
 {
+    let mut acc = fold(init, 0, &initial_element);
+
     let mut seen_states = HashMap::new();
     seen_states.insert(initial_element.clone(), 0);
 
@@ -102,14 +237,17 @@ where
 
         if let Some(prev_cycles) = seen_states.insert(curr.clone(), curr_cycles) {
             let length = curr_cycles - prev_cycles;
-            return CycleInfo {
+            let cycle_info = CycleInfo {
                 start: prev_cycles,
                 length,
 
                 initial_element,
                 first_element_in_cycle: curr,
             };
+            return (cycle_info, acc);
         }
+
+        acc = fold(acc, curr_cycles, &curr);
     }
 
     unreachable!()
@@ -135,3 +273,165 @@ pub fn apply_iterations_using_cycle_skip<S>(
 
     curr
 }
+
+// How many times each state is reached over the given number of iterations,
+// not counting the initial state. States are identified by the iteration
+// they were first seen at, as passed to hashmap_fold, so are all below
+// start + length. States that are never reached are skipped.
+pub fn visit_counts<S>(
+    cycle_info: &CycleInfo<S>,
+    iterations: u64,
+) -> impl Iterator<Item = (u64, u64)> {
+    let CycleInfo { start, length, .. } = *cycle_info;
+
+    let prefix = (1..start.min(iterations + 1)).map(|i| (i, 1));
+
+    // Iterations from first_in_cycle onwards all land in the cycle, starting
+    // from the state at offset first_offset into it.
+    let first_in_cycle = start.max(1);
+    let visits = (iterations + 1).saturating_sub(first_in_cycle);
+    let first_offset = (first_in_cycle - start) % length;
+
+    let cycle = (0..length.min(visits)).map(move |i| {
+        let offset = (first_offset + i) % length;
+        let count = visits / length + u64::from(i < visits % length);
+        (start + offset, count)
+    });
+
+    prefix.chain(cycle)
+}
+
+// Sums a value over the states reached by each of the given number of
+// iterations, using values[i] for the state first seen after i iterations.
+// repeat(value, n) should give the total of value repeated n times.
+pub fn sum_over_iterations<S, T, A>(
+    cycle_info: &CycleInfo<S>,
+    values: &[T],
+    iterations: u64,
+    repeat: impl Fn(&T, u64) -> A,
+) -> A
+where
+    A: Sum,
+{
+    visit_counts(cycle_info, iterations)
+        .map(|(i, count)| repeat(&values[i as usize], count))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 2, so a prefix of 2 and a cycle of 3.
+    fn with_prefix(x: u64) -> u64 {
+        [1, 2, 3, 4, 2][x as usize]
+    }
+
+    // 0 -> 1 -> 2 -> 0, cycling from the very start.
+    fn no_prefix(x: u64) -> u64 {
+        (x + 1) % 3
+    }
+
+    // The index each state is first seen at, and how often each index is
+    // reached over the given iterations, by walking them all.
+    fn walk_visit_counts(next: impl Fn(u64) -> u64, iterations: u64) -> Vec<(u64, u64)> {
+        let mut first_seen = HashMap::new();
+        let mut state = 0;
+        for i in 0..=iterations + 10 {
+            first_seen.entry(state).or_insert(i);
+            state = next(state);
+        }
+
+        let mut counts = HashMap::new();
+        let mut state = 0;
+        for _ in 0..iterations {
+            state = next(state);
+            *counts.entry(first_seen[&state]).or_insert(0) += 1;
+        }
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort();
+        counts
+    }
+
+    #[test]
+    fn detectors_agree() {
+        for (next, start, length) in [(with_prefix as fn(u64) -> u64, 2, 3), (no_prefix, 0, 3)] {
+            for info in [floyd(0, next), brent(0, next), hashmap(0, next)] {
+                assert_eq!((info.start, info.length), (start, length));
+                assert_eq!(info.first_element_in_cycle, start);
+            }
+
+            for info in [
+                floyd_by_key(0, next, |&x| x),
+                brent_by_key(0, next, |&x| x),
+                hashmap_by_key(0, next, |&x| x),
+            ] {
+                assert_eq!((info.start, info.length), (start, length));
+                assert_eq!(info.first_element_in_cycle, start);
+            }
+        }
+    }
+
+    #[test]
+    fn folds_see_each_distinct_state_once() {
+        for next in [with_prefix as fn(u64) -> u64, no_prefix] {
+            let collect = |mut seen: Vec<_>, i, &x: &u64| {
+                seen.push((i, x));
+                seen
+            };
+
+            let (info, brent_seen) = brent_fold(0, next, Vec::new(), collect);
+            let (_, hashmap_seen) = hashmap_fold(0, next, Vec::new(), collect);
+
+            let expected: Vec<_> = (0..info.start + info.length)
+                .map(|i| (i, apply_iterations_using_cycle_skip(info, i, next)))
+                .collect();
+            assert_eq!(brent_seen, expected);
+            assert_eq!(hashmap_seen, expected);
+        }
+    }
+
+    #[test]
+    fn visit_counts_match_walking() {
+        for next in [with_prefix as fn(u64) -> u64, no_prefix] {
+            let info = brent(0, next);
+
+            // Covers stopping inside the prefix as well as partway through
+            // several cycles.
+            for iterations in 0..20 {
+                let mut counts: Vec<_> = visit_counts(&info, iterations).collect();
+                counts.sort();
+                assert_eq!(
+                    counts,
+                    walk_visit_counts(next, iterations),
+                    "{iterations} iterations"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sum_over_iterations_matches_walking() {
+        for next in [with_prefix as fn(u64) -> u64, no_prefix] {
+            let (info, values) = brent_fold(0, next, Vec::new(), |mut values, _, &x| {
+                values.push(x * 10 + 1);
+                values
+            });
+
+            for iterations in 0..20 {
+                let walked: u64 = std::iter::successors(Some(0), |&x| Some(next(x)))
+                    .skip(1)
+                    .take(iterations as usize)
+                    .map(|x| x * 10 + 1)
+                    .sum();
+
+                assert_eq!(
+                    sum_over_iterations(&info, &values, iterations, |&v, n| v * n),
+                    walked,
+                    "{iterations} iterations"
+                );
+            }
+        }
+    }
+}
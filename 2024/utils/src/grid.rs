@@ -1,8 +1,60 @@
-use std::fmt::Debug;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug};
 use std::hash::Hash;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
+// Operations shared by the planar direction types, whose variants are
+// numbered clockwise so that rotation is just modular arithmetic.
+pub trait Direction: Copy + Eq + Into<u8> + TryFrom<u8> {
+    // Number of directions in a full turn.
+    const COUNT: u8;
+
+    type Delta;
+
+    fn delta(self) -> Self::Delta;
+
+    fn index(self) -> u8 {
+        self.into()
+    }
+
+    fn from_index(i: u8) -> Self {
+        Self::try_from(i % Self::COUNT)
+            .ok()
+            .expect("directions are numbered contiguously from 0")
+    }
+
+    fn all() -> impl Iterator<Item = Self> {
+        (0..Self::COUNT).map(Self::from_index)
+    }
+
+    fn rotate_cw(self) -> Self {
+        self.rotate_cw_by(1)
+    }
+
+    fn rotate_ccw(self) -> Self {
+        self.rotate_ccw_by(1)
+    }
+
+    fn rotate_cw_by(self, x: u8) -> Self {
+        Self::from_index(self.index() + x % Self::COUNT)
+    }
+
+    fn rotate_ccw_by(self, x: u8) -> Self {
+        self.rotate_cw_by(Self::COUNT - x % Self::COUNT)
+    }
+
+    fn flip(self) -> Self {
+        self.rotate_cw_by(Self::COUNT / 2)
+    }
+
+    // The number of clockwise steps needed to turn from self to other.
+    fn angle_between(self, other: Self) -> u8 {
+        (other.index() + Self::COUNT - self.index()) % Self::COUNT
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum OrthoDir {
@@ -20,42 +72,30 @@ impl OrthoDir {
     pub const DOWN: Self = Self::South;
     pub const LEFT: Self = Self::West;
 
-    pub fn delta(self) -> (isize, isize) {
-        match self {
-            OrthoDir::North => (0, -1),
-            OrthoDir::East => (1, 0),
-            OrthoDir::South => (0, 1),
-            OrthoDir::West => (-1, 0),
-        }
-    }
-
-    pub fn step(self, pos: (isize, isize)) -> (isize, isize) {
-        let (dx, dy) = self.delta();
-        (pos.0 + dx, pos.1 + dy)
+    pub fn step(self, pos: impl Into<Point2>) -> Point2 {
+        pos.into() + self.delta()
     }
 
-    pub fn step_n(self, pos: (isize, isize), n: isize) -> (isize, isize) {
-        let (dx, dy) = self.delta();
-        (pos.0 + dx * n, pos.1 + dy * n)
-    }
-
-    pub fn rotate_cw(self) -> Self {
-        self.rotate_cw_by(1)
+    pub fn step_n(self, pos: impl Into<Point2>, n: isize) -> Point2 {
+        pos.into() + self.delta() * n
     }
+}
 
-    pub fn rotate_ccw(self) -> Self {
-        self.rotate_ccw_by(1)
-    }
+impl Direction for OrthoDir {
+    const COUNT: u8 = 4;
 
-    pub fn rotate_cw_by(self, x: u8) -> Self {
-        ((u8::from(self) + x) % 4).try_into().unwrap()
-    }
+    type Delta = Point2;
 
-    pub fn rotate_ccw_by(self, x: u8) -> Self {
-        self.rotate_cw_by(4 - (x % 4))
+    fn delta(self) -> Point2 {
+        match self {
+            OrthoDir::North => Point2::new(0, -1),
+            OrthoDir::East => Point2::new(1, 0),
+            OrthoDir::South => Point2::new(0, 1),
+            OrthoDir::West => Point2::new(-1, 0),
+        }
     }
 
-    pub fn flip(self) -> Self {
+    fn flip(self) -> Self {
         match self {
             OrthoDir::North => OrthoDir::South,
             OrthoDir::East => OrthoDir::West,
@@ -95,55 +135,1826 @@ impl DiagDir {
     pub const DOWN: Self = Self::South;
     pub const LEFT: Self = Self::West;
 
-    fn delta(self) -> (isize, isize) {
+    pub fn step(self, pos: impl Into<Point2>) -> Point2 {
+        pos.into() + self.delta()
+    }
+
+    pub fn step_n(self, pos: impl Into<Point2>, n: isize) -> Point2 {
+        pos.into() + self.delta() * n
+    }
+}
+
+impl Direction for DiagDir {
+    const COUNT: u8 = 8;
+
+    type Delta = Point2;
+
+    fn delta(self) -> Point2 {
+        match self {
+            DiagDir::North => Point2::new(0, -1),
+            DiagDir::NorthEast => Point2::new(1, -1),
+            DiagDir::East => Point2::new(1, 0),
+            DiagDir::SouthEast => Point2::new(1, 1),
+            DiagDir::South => Point2::new(0, 1),
+            DiagDir::SouthWest => Point2::new(-1, 1),
+            DiagDir::West => Point2::new(-1, 0),
+            DiagDir::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    fn flip(self) -> Self {
         match self {
-            DiagDir::North => (0, -1),
-            DiagDir::NorthEast => (1, -1),
-            DiagDir::East => (1, 0),
-            DiagDir::SouthEast => (1, 1),
-            DiagDir::South => (0, 1),
-            DiagDir::SouthWest => (-1, 1),
-            DiagDir::West => (-1, 0),
-            DiagDir::NorthWest => (-1, -1),
+            DiagDir::North => DiagDir::South,
+            DiagDir::NorthEast => DiagDir::SouthWest,
+            DiagDir::East => DiagDir::West,
+            DiagDir::SouthEast => DiagDir::NorthWest,
+            DiagDir::South => DiagDir::North,
+            DiagDir::SouthWest => DiagDir::NorthEast,
+            DiagDir::West => DiagDir::East,
+            DiagDir::NorthWest => DiagDir::SouthEast,
         }
     }
+}
+
+// A position or offset on a 2D grid, with y increasing downwards to match
+// OrthoDir and DiagDir.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, dir: impl Into<Point2>) -> Self {
+        self + dir.into()
+    }
+
+    pub fn step_n(self, dir: impl Into<Point2>, n: isize) -> Self {
+        self + dir.into() * n
+    }
 
-    pub fn step(self, pos: (isize, isize)) -> (isize, isize) {
-        let (dx, dy) = self.delta();
-        (pos.0 + dx, pos.1 + dy)
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
-    pub fn step_n(self, pos: (isize, isize), n: isize) -> (isize, isize) {
-        let (dx, dy) = self.delta();
-        (pos.0 + dx * n, pos.1 + dy * n)
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
     }
 
+    // Rotates a quarter turn about the origin, clockwise as seen on screen.
     pub fn rotate_cw(self) -> Self {
-        self.rotate_cw_by(1)
+        Self::new(-self.y, self.x)
     }
 
     pub fn rotate_ccw(self) -> Self {
-        self.rotate_ccw_by(1)
+        Self::new(self.y, -self.x)
     }
 
-    pub fn rotate_cw_by(self, x: u8) -> Self {
-        ((u8::from(self) + x) % 8).try_into().unwrap()
+    pub fn rotate_cw_by(self, quarter_turns: u8) -> Self {
+        (0..quarter_turns % 4).fold(self, |p, _| p.rotate_cw())
     }
 
-    pub fn rotate_ccw_by(self, x: u8) -> Self {
-        self.rotate_cw_by(4 - (x % 4))
+    pub fn rotate_ccw_by(self, quarter_turns: u8) -> Self {
+        self.rotate_cw_by(4 - quarter_turns % 4)
     }
+}
 
-    pub fn flip(self) -> Self {
-        match self {
-            DiagDir::North => DiagDir::South,
-            DiagDir::NorthEast => DiagDir::SouthWest,
-            DiagDir::East => DiagDir::West,
-            DiagDir::SouthEast => DiagDir::NorthWest,
-            DiagDir::South => DiagDir::North,
-            DiagDir::SouthWest => DiagDir::NorthEast,
-            DiagDir::West => DiagDir::East,
-            DiagDir::NorthWest => DiagDir::SouthEast,
+impl From<(isize, isize)> for Point2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Point2> for (isize, isize) {
+    fn from(p: Point2) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<OrthoDir> for Point2 {
+    fn from(dir: OrthoDir) -> Self {
+        dir.delta()
+    }
+}
+
+impl From<DiagDir> for Point2 {
+    fn from(dir: DiagDir) -> Self {
+        dir.delta()
+    }
+}
+
+impl Add for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point2 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+// A dense, rectangular grid of cells addressed by (x, y) coordinates, with the
+// origin in the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for row in rows {
+            let len_before = cells.len();
+            cells.extend(row);
+
+            let row_width = cells.len() - len_before;
+            assert_eq!(
+                *width.get_or_insert(row_width),
+                row_width,
+                "row {height} has a different width to the rows before it"
+            );
+            height += 1;
+        }
+
+        Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        }
+    }
+
+    // Parses a block of text with one row per line, converting each character
+    // with f. Every line must have the same number of characters.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        Self::from_rows(
+            input
+                .lines()
+                .map(|line| line.chars().map(&mut f).collect::<Vec<_>>()),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: impl Into<Point2>) -> bool {
+        let Point2 { x, y } = pos.into();
+        (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y)
+    }
+
+    fn index_of(&self, pos: impl Into<Point2>) -> Option<usize> {
+        let pos = pos.into();
+        if self.in_bounds(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: impl Into<Point2>) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Point2>) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point2> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point2::new(x as isize, y as isize)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, value: &T) -> Option<Point2>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    // Traces the path of cells satisfying is_path from start, leaving in the
+    // direction heading. See trace for the details.
+    pub fn trace(
+        &self,
+        start: impl Into<Point2>,
+        heading: OrthoDir,
+        is_path: impl Fn(&T) -> bool,
+    ) -> Result<Trace<'_, T>, TraceError> {
+        trace(start.into(), heading, |pos| {
+            self.get(pos).filter(|cell| is_path(cell))
+        })
+    }
+
+    pub fn wrapping(&self, wrap: Wrap) -> Wrapping<'_, T> {
+        Wrapping { grid: self, wrap }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // The number of steps in dirs from each cell to the nearest boundary cell,
+    // found with a single breadth-first search seeded from every boundary cell
+    // at once. Boundary cells have depth 0, and the area outside the grid
+    // counts as boundary too, so cells along the edge are at most 1 deep.
+    pub fn distance_transform<D>(
+        &self,
+        dirs: &[D],
+        mut is_boundary: impl FnMut(&T) -> bool,
+    ) -> Grid<usize>
+    where
+        D: Direction<Delta = Point2>,
+    {
+        let mut depths = Grid::new(self.width, self.height, usize::MAX);
+        let mut queue = VecDeque::new();
+
+        for (pos, cell) in self.iter() {
+            if is_boundary(cell) {
+                depths[pos] = 0;
+                queue.push_back(pos);
+            }
+        }
+
+        // Cells next to the edge have to be seeded after all the boundary
+        // cells so that the queue stays in order of depth.
+        for pos in self.positions() {
+            let depth = &mut depths[pos];
+            if *depth == usize::MAX && dirs.iter().any(|&dir| !self.in_bounds(pos + dir.delta())) {
+                *depth = 1;
+                queue.push_back(pos);
+            }
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            let depth = depths[pos];
+
+            for &dir in dirs {
+                let next = pos + dir.delta();
+                if depths.get(next) == Some(&usize::MAX) {
+                    depths[next] = depth + 1;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        depths
+    }
+
+    // Splits the grid into connected regions, where two cells a step apart in
+    // dirs are connected if same_region holds for them. Every cell ends up in
+    // exactly one region, labelled by its index in the order they're found.
+    pub fn regions<D>(&self, dirs: &[D], mut same_region: impl FnMut(&T, &T) -> bool) -> Regions
+    where
+        D: Direction<Delta = Point2>,
+    {
+        let mut labels = Grid::new(self.width, self.height, usize::MAX);
+        let mut regions = Vec::new();
+        let mut stack = Vec::new();
+
+        for start in self.positions() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region {
+                size: 0,
+                bounds: Bounds::point(start),
+                perimeter: 0,
+            };
+
+            labels[start] = label;
+            stack.push(start);
+
+            while let Some(pos) = stack.pop() {
+                region.size += 1;
+                region.bounds.extend(pos);
+
+                for &dir in dirs {
+                    let next = pos + dir.delta();
+                    if labels.get(next) == Some(&usize::MAX) && same_region(&self[pos], &self[next])
+                    {
+                        labels[next] = label;
+                        stack.push(next);
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        // Perimeters are measured along cell edges whatever the connectivity,
+        // so they can only be counted once every cell has its final label.
+        for (pos, &label) in labels.iter() {
+            regions[label].perimeter += OrthoDir::ALL
+                .iter()
+                .filter(|&&dir| labels.get(dir.step(pos)) != Some(&label))
+                .count();
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, |c| c)
+    }
+}
+
+impl<T, P: Into<Point2>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T, P: Into<Point2>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+        let pos = pos.into();
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for c in row {
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Which edges of a grid join up with their opposite edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    None,
+    // Left and right edges join, making a cylinder.
+    Horizontal,
+    // Top and bottom edges join, making a cylinder.
+    Vertical,
+    // Both pairs of edges join, making a torus.
+    Both,
+}
+
+impl Wrap {
+    fn horizontal(self) -> bool {
+        matches!(self, Wrap::Horizontal | Wrap::Both)
+    }
+
+    fn vertical(self) -> bool {
+        matches!(self, Wrap::Vertical | Wrap::Both)
+    }
+}
+
+// A view of a grid where coordinates that fall off a wrapping edge come back
+// in on the opposite side.
+#[derive(Debug)]
+pub struct Wrapping<'a, T> {
+    grid: &'a Grid<T>,
+    wrap: Wrap,
+}
+
+impl<T> Clone for Wrapping<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Wrapping<'_, T> {}
+
+impl<'a, T> Wrapping<'a, T> {
+    pub fn new(grid: &'a Grid<T>, wrap: Wrap) -> Self {
+        Self { grid, wrap }
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    // Maps a position onto its canonical coordinates along any wrapping axes.
+    // Non-wrapping axes are left as-is, so the result may still be outside the
+    // grid.
+    pub fn wrap(&self, pos: impl Into<Point2>) -> Point2 {
+        let Point2 { mut x, mut y } = pos.into();
+        if self.wrap.horizontal() && self.grid.width() > 0 {
+            x = x.rem_euclid(self.grid.width() as isize);
+        }
+        if self.wrap.vertical() && self.grid.height() > 0 {
+            y = y.rem_euclid(self.grid.height() as isize);
+        }
+        Point2::new(x, y)
+    }
+
+    pub fn get(&self, pos: impl Into<Point2>) -> Option<&'a T> {
+        self.grid.get(self.wrap(pos))
+    }
+
+    // Steps from pos in the given direction, returning None if that walks off
+    // a non-wrapping edge.
+    pub fn step<D>(&self, pos: impl Into<Point2>, dir: D) -> Option<Point2>
+    where
+        D: Direction<Delta = Point2>,
+    {
+        let next = self.wrap(pos.into() + dir.delta());
+        self.grid.in_bounds(next).then_some(next)
+    }
+
+    // All positions visited by repeatedly stepping in a direction from start,
+    // starting with start itself. Ends when walking off a non-wrapping edge,
+    // and never ends if every axis the direction moves along wraps.
+    pub fn walk<D>(&self, start: impl Into<Point2>, dir: D) -> impl Iterator<Item = Point2> + 'a
+    where
+        D: Direction<Delta = Point2> + 'a,
+    {
+        let view = *self;
+        let start = Some(self.wrap(start)).filter(|&pos| self.grid.in_bounds(pos));
+        std::iter::successors(start, move |&pos| view.step(pos, dir))
+    }
+}
+
+impl<T, P: Into<Point2>> Index<P> for Wrapping<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+// An inclusive bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Bounds {
+    pub fn point(pos: impl Into<Point2>) -> Self {
+        let pos = pos.into();
+        Self { min: pos, max: pos }
+    }
+
+    pub fn extend(&mut self, pos: impl Into<Point2>) {
+        let pos = pos.into();
+        self.min = Point2::new(self.min.x.min(pos.x), self.min.y.min(pos.y));
+        self.max = Point2::new(self.max.x.max(pos.x), self.max.y.max(pos.y));
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn contains(&self, pos: impl Into<Point2>) -> bool {
+        let pos = pos.into();
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub size: usize,
+    pub bounds: Bounds,
+    // The number of cell edges separating the region from other regions or
+    // the outside of the grid.
+    pub perimeter: usize,
+}
+
+// The connected regions of a grid, along with the label of the region each
+// cell belongs to, which indexes into regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+// An unbounded grid that only stores the cells that have been set, keeping
+// track of the bounding box of everything inserted into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
         }
     }
 }
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Parses a block of text with one row per line, skipping any background
+    // characters and converting the rest with f.
+    pub fn parse_with(input: &str, background: char, mut f: impl FnMut(char) -> T) -> Self {
+        let mut grid = Self::new();
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c != background {
+                    grid.insert(Point2::new(x as isize, y as isize), f(c));
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn insert(&mut self, pos: impl Into<Point2>, value: T) -> Option<T> {
+        let pos = pos.into();
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(pos),
+            None => self.bounds = Some(Bounds::point(pos)),
+        }
+
+        self.cells.insert(pos, value)
+    }
+
+    // Removing a cell from the edge of the bounding box shrinks it to fit the
+    // cells that are left, which means looking at all of them.
+    pub fn remove(&mut self, pos: impl Into<Point2>) -> Option<T> {
+        let pos = pos.into();
+        let value = self.cells.remove(&pos)?;
+
+        let bounds = self.bounds.expect("a grid with cells has bounds");
+        if [bounds.min.x, bounds.max.x].contains(&pos.x)
+            || [bounds.min.y, bounds.max.y].contains(&pos.y)
+        {
+            self.bounds = self.cells.keys().fold(None, |bounds, &pos| {
+                let mut bounds = bounds.unwrap_or(Bounds::point(pos));
+                bounds.extend(pos);
+                Some(bounds)
+            });
+        }
+
+        Some(value)
+    }
+
+    pub fn get(&self, pos: impl Into<Point2>) -> Option<&T> {
+        self.cells.get(&pos.into())
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Point2>) -> Option<&mut T> {
+        self.cells.get_mut(&pos.into())
+    }
+
+    pub fn contains(&self, pos: impl Into<Point2>) -> bool {
+        self.cells.contains_key(&pos.into())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The smallest box containing every cell, or None if the grid is empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    // Traces the path of set cells from start, leaving in the direction
+    // heading. See trace for the details.
+    pub fn trace(
+        &self,
+        start: impl Into<Point2>,
+        heading: OrthoDir,
+    ) -> Result<Trace<'_, T>, TraceError> {
+        trace(start.into(), heading, |pos| self.get(pos))
+    }
+}
+
+impl SparseGrid<char> {
+    pub fn parse(input: &str, background: char) -> Self {
+        Self::parse_with(input, background, |c| c)
+    }
+
+    // Renders the cells within the bounding box back to text, filling in any
+    // unset cells with the background character.
+    pub fn render(&self, background: char) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let mut output = String::new();
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                output.push(self.get((x, y)).copied().unwrap_or(background));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+impl<T, P: Into<Point2>> FromIterator<(P, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (P, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
+
+impl<T, P: Into<Point2>> Index<P> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos)
+            .unwrap_or_else(|| panic!("nothing at {pos:?} in the grid"))
+    }
+}
+
+// A route traced along a single-width path of cells, starting from the cell
+// it was traced from. A closed path ends at the cell just before the start,
+// which isn't repeated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<'a, T> {
+    pub cells: Vec<(Point2, &'a T)>,
+    pub closed: bool,
+}
+
+impl<T> Trace<'_, T> {
+    // Passes the trace on only if it loops back to its start.
+    pub fn into_loop(self) -> Result<Self, TraceError> {
+        if self.closed {
+            Ok(self)
+        } else {
+            let (pos, _) = self.cells[self.cells.len() - 1];
+            Err(TraceError::DeadEnd(pos))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceError {
+    // The path ran out at this cell.
+    DeadEnd(Point2),
+    // The path could continue in more than one direction from this cell.
+    Branch(Point2),
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::DeadEnd(pos) => {
+                write!(f, "path comes to a dead end at ({}, {})", pos.x, pos.y)
+            }
+            TraceError::Branch(pos) => write!(f, "path branches at ({}, {})", pos.x, pos.y),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+// Follows a path from start, first stepping in heading and then going
+// straight on or turning left or right, until it either returns to start or
+// has nowhere left to go. cell gives the contents of each cell on the path,
+// and None for everything else.
+fn trace<'a, T: 'a>(
+    start: Point2,
+    heading: OrthoDir,
+    cell: impl Fn(Point2) -> Option<&'a T>,
+) -> Result<Trace<'a, T>, TraceError> {
+    let mut cells = vec![(start, cell(start).ok_or(TraceError::DeadEnd(start))?)];
+
+    let mut pos = start;
+    let mut candidates = vec![heading];
+
+    loop {
+        let mut next = candidates
+            .into_iter()
+            .filter_map(|dir| Some((dir, cell(dir.step(pos))?)));
+
+        let Some((next_dir, value)) = next.next() else {
+            // Having nowhere to go from the start is a dead end, rather than
+            // a path that's just one cell long.
+            if pos == start {
+                return Err(TraceError::DeadEnd(start));
+            }
+            return Ok(Trace {
+                cells,
+                closed: false,
+            });
+        };
+        if next.next().is_some() {
+            return Err(TraceError::Branch(pos));
+        }
+
+        pos = next_dir.step(pos);
+
+        if pos == start {
+            return Ok(Trace {
+                cells,
+                closed: true,
+            });
+        }
+
+        cells.push((pos, value));
+        candidates = vec![next_dir, next_dir.rotate_ccw(), next_dir.rotate_cw()];
+    }
+}
+
+// Directions between neighbouring cells of a hex grid with pointy-topped
+// hexes, so that rows run horizontally and y (or r) increases downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum HexDir {
+    NorthEast,
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl HexDir {
+    pub const ALL: [Self; 6] = [
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    pub const RIGHT: Self = Self::East;
+    pub const LEFT: Self = Self::West;
+
+    pub fn step(self, pos: Hex) -> Hex {
+        pos + self.delta()
+    }
+
+    pub fn step_n(self, pos: Hex, n: isize) -> Hex {
+        pos + self.delta() * n
+    }
+}
+
+impl Direction for HexDir {
+    const COUNT: u8 = 6;
+
+    type Delta = Hex;
+
+    fn delta(self) -> Hex {
+        match self {
+            HexDir::NorthEast => Hex::new(1, -1),
+            HexDir::East => Hex::new(1, 0),
+            HexDir::SouthEast => Hex::new(0, 1),
+            HexDir::SouthWest => Hex::new(-1, 1),
+            HexDir::West => Hex::new(-1, 0),
+            HexDir::NorthWest => Hex::new(0, -1),
+        }
+    }
+}
+
+// How a hex grid is laid out as rows of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexLayout {
+    // Every other row, starting with row 1, is shifted half a cell right.
+    OddR,
+    // Every other row, starting with row 0, is shifted half a cell right.
+    EvenR,
+    // Each hex is two characters wide, so neighbouring rows are offset by one
+    // character and every other column in a row is padding.
+    DoubledWidth,
+}
+
+// A cell in a hex grid, in axial coordinates. The third cube coordinate s is
+// implied by q + r + s = 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn from_cube((q, r, s): (isize, isize, isize)) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must sum to 0");
+        Self::new(q, r)
+    }
+
+    pub fn to_cube(self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    // Converts from (column, row) position in a text map.
+    pub fn from_offset(pos: impl Into<Point2>, layout: HexLayout) -> Self {
+        let Point2 { x: col, y: row } = pos.into();
+        let q = match layout {
+            HexLayout::OddR => col - (row - (row & 1)) / 2,
+            HexLayout::EvenR => col - (row + (row & 1)) / 2,
+            HexLayout::DoubledWidth => (col - row).div_euclid(2),
+        };
+        Self::new(q, row)
+    }
+
+    // Converts to a (column, row) position in a text map.
+    pub fn to_offset(self, layout: HexLayout) -> Point2 {
+        let col = match layout {
+            HexLayout::OddR => self.q + (self.r - (self.r & 1)) / 2,
+            HexLayout::EvenR => self.q + (self.r + (self.r & 1)) / 2,
+            HexLayout::DoubledWidth => 2 * self.q + self.r,
+        };
+        Point2::new(col, self.r)
+    }
+
+    pub fn distance(self, other: Self) -> usize {
+        let (dq, dr, ds) = (self - other).to_cube();
+        (dq.unsigned_abs() + dr.unsigned_abs() + ds.unsigned_abs()) / 2
+    }
+
+    // Rotates a sixth of a turn clockwise about the origin.
+    pub fn rotate_cw(self) -> Self {
+        let (q, r, s) = self.to_cube();
+        Self::from_cube((-r, -s, -q))
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        let (q, r, s) = self.to_cube();
+        Self::from_cube((-s, -q, -r))
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+// A position or offset in 3D space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    // Offsets to every cell sharing a face, edge or corner with a cell.
+    pub const NEIGHBOURS_26: [Self; 26] = neighbours_26();
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn step(self, dir: Dir3) -> Self {
+        self + dir.delta()
+    }
+
+    pub fn step_n(self, dir: Dir3, n: isize) -> Self {
+        self + dir.delta() * n
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    // Rotates a quarter turn about the given axis through the origin,
+    // anticlockwise when looking down the axis towards the origin (i.e. by the
+    // right-hand rule).
+    pub fn rotate_about(self, axis: Axis) -> Self {
+        let Self { x, y, z } = self;
+        match axis {
+            Axis::X => Self::new(x, -z, y),
+            Axis::Y => Self::new(z, y, -x),
+            Axis::Z => Self::new(-y, x, z),
+        }
+    }
+
+    pub fn rotate_about_by(self, axis: Axis, quarter_turns: u8) -> Self {
+        (0..quarter_turns % 4).fold(self, |p, _| p.rotate_about(axis))
+    }
+}
+
+const fn neighbours_26() -> [Point3; 26] {
+    let mut neighbours = [Point3::ORIGIN; 26];
+    let mut i = 0;
+    let mut n = 0;
+    while n < 27 {
+        // Skip (0, 0, 0), which is the 14th combination.
+        if n != 13 {
+            let (x, y, z) = (n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+            neighbours[i] = Point3::new(x, y, z);
+            i += 1;
+        }
+        n += 1;
+    }
+    neighbours
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Point3> for (isize, isize, isize) {
+    fn from(p: Point3) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// The six face-adjacent directions in 3D.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum Dir3 {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl Dir3 {
+    pub const ALL: [Self; 6] = [
+        Self::PosX,
+        Self::NegX,
+        Self::PosY,
+        Self::NegY,
+        Self::PosZ,
+        Self::NegZ,
+    ];
+
+    pub fn delta(self) -> Point3 {
+        match self {
+            Dir3::PosX => Point3::new(1, 0, 0),
+            Dir3::NegX => Point3::new(-1, 0, 0),
+            Dir3::PosY => Point3::new(0, 1, 0),
+            Dir3::NegY => Point3::new(0, -1, 0),
+            Dir3::PosZ => Point3::new(0, 0, 1),
+            Dir3::NegZ => Point3::new(0, 0, -1),
+        }
+    }
+
+    pub fn from_delta(delta: Point3) -> Option<Self> {
+        Self::ALL.into_iter().find(|dir| dir.delta() == delta)
+    }
+
+    pub fn axis(self) -> Axis {
+        match self {
+            Dir3::PosX | Dir3::NegX => Axis::X,
+            Dir3::PosY | Dir3::NegY => Axis::Y,
+            Dir3::PosZ | Dir3::NegZ => Axis::Z,
+        }
+    }
+
+    pub fn step(self, pos: Point3) -> Point3 {
+        pos + self.delta()
+    }
+
+    pub fn step_n(self, pos: Point3, n: isize) -> Point3 {
+        pos + self.delta() * n
+    }
+
+    // See Point3::rotate_about for the sense of the rotation.
+    pub fn rotate_about(self, axis: Axis) -> Self {
+        Self::from_delta(self.delta().rotate_about(axis)).unwrap()
+    }
+
+    pub fn rotate_about_by(self, axis: Axis, quarter_turns: u8) -> Self {
+        Self::from_delta(self.delta().rotate_about_by(axis, quarter_turns)).unwrap()
+    }
+
+    pub fn flip(self) -> Self {
+        match self {
+            Dir3::PosX => Dir3::NegX,
+            Dir3::NegX => Dir3::PosX,
+            Dir3::PosY => Dir3::NegY,
+            Dir3::NegY => Dir3::PosY,
+            Dir3::PosZ => Dir3::NegZ,
+            Dir3::NegZ => Dir3::PosZ,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn check_rotation_algebra<D: Direction + Debug>() {
+        assert_eq!(D::all().count(), D::COUNT as usize);
+
+        for dir in D::all() {
+            assert_eq!(dir.rotate_cw().rotate_ccw(), dir);
+            assert_eq!(dir.rotate_ccw().rotate_cw(), dir);
+            assert_eq!(dir.flip(), dir.rotate_cw_by(D::COUNT / 2));
+            assert_eq!(dir.flip(), dir.rotate_ccw_by(D::COUNT / 2));
+            assert_eq!(dir.flip().flip(), dir);
+            assert_eq!(dir.rotate_cw_by(D::COUNT), dir);
+
+            for x in 0..=u8::MAX {
+                let rotated = dir.rotate_cw_by(x);
+                assert_eq!(rotated.rotate_ccw_by(x), dir, "{dir:?} by {x}");
+                assert_eq!(dir.rotate_ccw_by(x).rotate_cw_by(x), dir, "{dir:?} by {x}");
+                assert_eq!(
+                    rotated,
+                    (0..x).fold(dir, |d, _| d.rotate_cw()),
+                    "{dir:?} by {x}"
+                );
+                assert_eq!(dir.angle_between(rotated), x % D::COUNT, "{dir:?} by {x}");
+            }
+
+            for other in D::all() {
+                assert_eq!(dir.rotate_cw_by(dir.angle_between(other)), other);
+                assert_eq!(
+                    (dir.angle_between(other) + other.angle_between(dir)) % D::COUNT,
+                    0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ortho_dir_rotations() {
+        check_rotation_algebra::<OrthoDir>();
+
+        for dir in OrthoDir::ALL {
+            let delta = Point2::from(dir);
+            assert_eq!(Point2::from(dir.rotate_cw()), delta.rotate_cw());
+            assert_eq!(Point2::from(dir.rotate_ccw()), delta.rotate_ccw());
+            assert_eq!(Point2::from(dir.flip()), -delta);
+        }
+    }
+
+    #[test]
+    fn diag_dir_rotations() {
+        check_rotation_algebra::<DiagDir>();
+
+        for dir in DiagDir::ALL {
+            // Two eighth turns make a quarter turn.
+            let delta = Point2::from(dir);
+            assert_eq!(Point2::from(dir.rotate_cw_by(2)), delta.rotate_cw());
+            assert_eq!(Point2::from(dir.rotate_ccw_by(2)), delta.rotate_ccw());
+            assert_eq!(Point2::from(dir.flip()), -delta);
+        }
+    }
+
+    #[test]
+    fn hex_dir_rotations() {
+        check_rotation_algebra::<HexDir>();
+
+        for dir in HexDir::ALL {
+            let delta = dir.delta();
+            assert_eq!(dir.rotate_cw().delta(), delta.rotate_cw());
+            assert_eq!(dir.rotate_ccw().delta(), delta.rotate_ccw());
+            assert_eq!(dir.flip().delta(), -delta);
+        }
+    }
+
+    #[test]
+    fn grid_parse_and_lookup() {
+        let grid = Grid::parse("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+
+        assert_eq!(grid.get((0, 0)), Some(&'a'));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid[(1, 1)], 'e');
+        assert_eq!(grid[Point2::new(1, 0)], 'b');
+
+        assert!(grid.in_bounds((2, 1)));
+        assert!(!grid.in_bounds((2, -1)));
+
+        assert_eq!(grid.find(&'e'), Some(Point2::new(1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn grid_rows_columns_and_positions() {
+        let grid = Grid::parse_with("123\n456", |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert!(grid.rows().eq([&[1, 2, 3][..], &[4, 5, 6][..]]));
+        assert!(grid.column(2).eq(&[3, 6]));
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+
+        assert!(grid.positions().map(<(isize, isize)>::from).eq([
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (0, 1),
+            (1, 1),
+            (2, 1)
+        ]));
+        assert!(grid.iter().all(|(pos, &value)| grid[pos] == value));
+    }
+
+    #[test]
+    fn grid_mutation_and_map() {
+        let mut grid = Grid::new(2, 2, 0);
+        *grid.get_mut((1, 0)).unwrap() = 5;
+        grid[(0, 1)] = 7;
+        assert_eq!(grid.get_mut((2, 0)), None);
+
+        assert_eq!(grid, Grid::from_rows([[0, 5], [7, 0]]));
+        assert_eq!(grid.map(|x| x * 2), Grid::from_rows([[0, 10], [14, 0]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "different width")]
+    fn grid_rejects_ragged_rows() {
+        Grid::parse("abc\nde");
+    }
+
+    #[test]
+    fn grid_degenerate_sizes() {
+        let empty = Grid::parse("");
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.positions().count(), 0);
+        assert_eq!(empty.get((0, 0)), None);
+
+        // Columns but no rows.
+        let flat = Grid::new(3, 0, '.');
+        assert_eq!(flat.column(1).count(), 0);
+        assert_eq!(flat.columns().count(), 3);
+        assert_eq!(flat.rows().count(), 0);
+        assert_eq!(flat.positions().count(), 0);
+
+        // Rows but no columns.
+        let thin = Grid::from_rows(vec![Vec::<char>::new(); 2]);
+        assert_eq!((thin.width(), thin.height()), (0, 2));
+        assert!(thin.rows().all(|row| row.is_empty()));
+        assert_eq!(thin.columns().count(), 0);
+        assert_eq!(thin.positions().count(), 0);
+
+        let single = Grid::new(1, 1, 'x');
+        assert!(single.column(0).eq(&['x']));
+        assert_eq!(single.find(&'x'), Some(Point2::ORIGIN));
+    }
+
+    #[test]
+    fn wrapping_coordinates() {
+        let grid = Grid::parse("abc\ndef");
+
+        let both = grid.wrapping(Wrap::Both);
+        assert_eq!(both.wrap((-1, -1)), Point2::new(2, 1));
+        assert_eq!(both.wrap((3 * 4 + 1, -2 * 5)), Point2::new(1, 0));
+        assert_eq!(both[(-7, 3)], 'f');
+        assert_eq!(both.get((300, -301)), Some(&'d'));
+
+        let horizontal = grid.wrapping(Wrap::Horizontal);
+        assert_eq!(horizontal.wrap((-4, 5)), Point2::new(2, 5));
+        assert_eq!(horizontal.get((-4, 1)), Some(&'f'));
+        assert_eq!(horizontal.get((0, 2)), None);
+        assert_eq!(horizontal.get((0, -1)), None);
+
+        let vertical = grid.wrapping(Wrap::Vertical);
+        assert_eq!(vertical.wrap((5, -3)), Point2::new(5, 1));
+        assert_eq!(vertical.get((1, -3)), Some(&'e'));
+        assert_eq!(vertical.get((3, 0)), None);
+        assert_eq!(vertical.get((-1, 0)), None);
+
+        let none = grid.wrapping(Wrap::None);
+        assert_eq!(none.wrap((-1, 7)), Point2::new(-1, 7));
+        assert_eq!(none.get((-1, 0)), None);
+        assert_eq!(none[(2, 1)], 'f');
+    }
+
+    #[test]
+    fn wrapping_steps_and_walks() {
+        let grid = Grid::parse("abc\ndef");
+
+        let horizontal = grid.wrapping(Wrap::Horizontal);
+        assert_eq!(
+            horizontal.step((0, 0), OrthoDir::West),
+            Some(Point2::new(2, 0))
+        );
+        assert_eq!(horizontal.step((0, 0), OrthoDir::North), None);
+        assert_eq!(
+            horizontal
+                .walk((1, 1), OrthoDir::East)
+                .take(5)
+                .map(|pos| horizontal[pos])
+                .collect::<String>(),
+            "efdef"
+        );
+        // Walking along a non-wrapping axis stops at the edge.
+        assert_eq!(horizontal.walk((1, 0), OrthoDir::South).count(), 2);
+        assert_eq!(horizontal.walk((1, 5), OrthoDir::South).count(), 0);
+
+        let vertical = grid.wrapping(Wrap::Vertical);
+        assert_eq!(
+            vertical
+                .walk((2, -1), DiagDir::SouthWest)
+                .map(|pos| vertical[pos])
+                .collect::<String>(),
+            "fbd"
+        );
+    }
+
+    #[test]
+    fn sparse_grid_bounds() {
+        let mut grid = SparseGrid::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render('.'), "");
+
+        grid.insert((1, 1), 'a');
+        assert_eq!(grid.bounds(), Some(Bounds::point((1, 1))));
+
+        grid.insert((-2, 3), 'b');
+        grid.insert((0, -4), 'c');
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point2::new(-2, -4), Point2::new(1, 3))
+        );
+        assert_eq!((bounds.width(), bounds.height()), (4, 8));
+        assert!(bounds.contains((-2, 0)));
+        assert!(!bounds.contains((2, 0)));
+
+        // Overwriting a cell keeps the old bounds.
+        assert_eq!(grid.insert((1, 1), 'd'), Some('a'));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some(bounds));
+
+        // Removing a cell shrinks the bounds if it was on their edge.
+        grid.insert((0, 0), 'e');
+        assert_eq!(grid.remove((0, 0)), Some('e'));
+        assert_eq!(grid.bounds(), Some(bounds));
+        assert_eq!(grid.remove((0, -4)), Some('c'));
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point2::new(-2, 1), Point2::new(1, 3))
+        );
+        assert_eq!(grid.remove((0, -4)), None);
+
+        assert_eq!(grid.remove((1, 1)), Some('d'));
+        assert_eq!(grid.bounds(), Some(Bounds::point((-2, 3))));
+        assert_eq!(grid.remove((-2, 3)), Some('b'));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn sparse_grid_parse_and_render() {
+        let grid = SparseGrid::parse("..#\n.#.\n", '.');
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains((2, 0)));
+        assert!(!grid.contains((0, 0)));
+        assert_eq!(grid[(1, 1)], '#');
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point2::new(1, 0), Point2::new(2, 1))
+        );
+        assert_eq!(grid.render(' '), " #\n# \n");
+
+        let shifted: SparseGrid<_> = grid
+            .iter()
+            .map(|(pos, &c)| (pos - Point2::new(3, 3), c))
+            .collect();
+        assert_eq!(shifted.get((-1, -3)), Some(&'#'));
+        assert_eq!(shifted.render('.'), ".#\n#.\n");
+    }
+
+    #[test]
+    fn hex_offsets_round_trip() {
+        for layout in [HexLayout::OddR, HexLayout::EvenR, HexLayout::DoubledWidth] {
+            for row in -5..=5 {
+                for col in -5..=5 {
+                    // Only every other column holds a hex in a doubled layout.
+                    if layout == HexLayout::DoubledWidth && (col + row) % 2 != 0 {
+                        continue;
+                    }
+
+                    let hex = Hex::from_offset((col, row), layout);
+                    assert_eq!(hex.r, row);
+                    assert_eq!(hex.to_offset(layout), Point2::new(col, row), "{layout:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hex_offsets_match_neighbours() {
+        // Where the hexes up and to the left and right of each position are in
+        // the text map, depending on whether its row is odd.
+        let above = |layout, row: isize| match (layout, row.rem_euclid(2)) {
+            (HexLayout::OddR, 1) | (HexLayout::EvenR, 0) => (0, 1),
+            (HexLayout::OddR, _) | (HexLayout::EvenR, _) => (-1, 0),
+            (HexLayout::DoubledWidth, _) => (-1, 1),
+        };
+
+        for layout in [HexLayout::OddR, HexLayout::EvenR, HexLayout::DoubledWidth] {
+            let east = if layout == HexLayout::DoubledWidth {
+                2
+            } else {
+                1
+            };
+
+            for row in -3..=3 {
+                let col = if layout == HexLayout::DoubledWidth {
+                    row
+                } else {
+                    0
+                };
+                let hex = Hex::from_offset((col, row), layout);
+                let (west_above, east_above) = above(layout, row);
+
+                let offset = |dir: HexDir| dir.step(hex).to_offset(layout);
+                assert_eq!(offset(HexDir::East), Point2::new(col + east, row));
+                assert_eq!(offset(HexDir::West), Point2::new(col - east, row));
+                assert_eq!(
+                    offset(HexDir::NorthWest),
+                    Point2::new(col + west_above, row - 1),
+                    "{layout:?} row {row}"
+                );
+                assert_eq!(
+                    offset(HexDir::NorthEast),
+                    Point2::new(col + east_above, row - 1),
+                    "{layout:?} row {row}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hex_distance_and_rotation() {
+        let hex = Hex::new(3, -1);
+        assert_eq!(hex.distance(Hex::ORIGIN), 3);
+        assert_eq!(Hex::ORIGIN.distance(hex), 3);
+        assert_eq!(hex.distance(hex), 0);
+        assert_eq!(Hex::new(-2, -2).distance(Hex::new(1, 1)), 6);
+        assert_eq!(HexDir::SouthWest.step_n(hex, 4), Hex::new(-1, 3));
+
+        // Rotating keeps the distance from the origin, and six turns go all the
+        // way round.
+        let mut rotated = hex;
+        for _ in 0..6 {
+            rotated = rotated.rotate_cw();
+            assert_eq!(rotated.distance(Hex::ORIGIN), 3);
+            assert_eq!(rotated.rotate_ccw().rotate_cw(), rotated);
+        }
+        assert_eq!(rotated, hex);
+        assert_eq!(Hex::new(1, 0).rotate_cw(), Hex::new(0, 1));
+        assert_eq!(Hex::new(1, 0).rotate_ccw(), Hex::new(1, -1));
+
+        for n in 0..4 {
+            for dir in HexDir::ALL {
+                assert_eq!(dir.step_n(hex, n).distance(hex), n as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn point3_rotations() {
+        let p = Point3::new(1, 2, 3);
+
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            assert_eq!(p.rotate_about_by(axis, 4), p);
+            assert_eq!(p.rotate_about_by(axis, 0), p);
+            assert_eq!(p.rotate_about_by(axis, 5), p.rotate_about(axis));
+
+            let turned = (0..4).fold(p, |q, _| q.rotate_about(axis));
+            assert_eq!(turned, p);
+
+            // Quarter turns keep distances, and half turns are their own
+            // inverse.
+            assert_eq!(p.rotate_about(axis).manhattan(Point3::ORIGIN), 6);
+            assert_eq!(p.rotate_about_by(axis, 2).rotate_about_by(axis, 2), p);
+        }
+
+        // By the right-hand rule, each axis turns the next one into the one
+        // after it.
+        let (x, y, z) = (Dir3::PosX.delta(), Dir3::PosY.delta(), Dir3::PosZ.delta());
+        assert_eq!(x.rotate_about(Axis::Z), y);
+        assert_eq!(y.rotate_about(Axis::X), z);
+        assert_eq!(z.rotate_about(Axis::Y), x);
+        assert_eq!(y.rotate_about(Axis::Z), -x);
+    }
+
+    #[test]
+    fn dir3_rotations_match_point3() {
+        for dir in Dir3::ALL {
+            assert_eq!(Dir3::from_delta(dir.delta()), Some(dir));
+            assert_eq!(dir.flip().delta(), -dir.delta());
+
+            for axis in [Axis::X, Axis::Y, Axis::Z] {
+                for turns in 0..5 {
+                    assert_eq!(
+                        dir.rotate_about_by(axis, turns).delta(),
+                        dir.delta().rotate_about_by(axis, turns)
+                    );
+                }
+                assert_eq!(
+                    dir.rotate_about(axis).delta(),
+                    dir.delta().rotate_about(axis)
+                );
+
+                // Turning about its own axis leaves a direction alone.
+                if dir.axis() == axis {
+                    assert_eq!(dir.rotate_about(axis), dir);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours_26_are_distinct() {
+        let neighbours: HashSet<_> = Point3::NEIGHBOURS_26.into_iter().collect();
+        assert_eq!(neighbours.len(), 26);
+        assert!(!neighbours.contains(&Point3::ORIGIN));
+        assert!(neighbours.iter().all(|&n| n.chebyshev(Point3::ORIGIN) == 1));
+    }
+
+    fn digits(input: &str) -> Grid<usize> {
+        Grid::parse_with(input, |c| c.to_digit(10).unwrap() as usize)
+    }
+
+    #[test]
+    fn distance_transform_edges_are_outside() {
+        // With no boundary cells at all, depths only come from the edges.
+        let solid = Grid::new(3, 5, '#');
+        let expected = digits("111\n121\n121\n121\n111");
+        assert_eq!(
+            solid.distance_transform(&OrthoDir::ALL, |&c| c == '.'),
+            expected
+        );
+        assert_eq!(
+            solid.distance_transform(&DiagDir::ALL, |&c| c == '.'),
+            expected
+        );
+
+        let empty = Grid::new(4, 2, '.');
+        let depths = empty.distance_transform(&OrthoDir::ALL, |&c| c == '.');
+        assert_eq!(depths, Grid::new(4, 2, 0));
+    }
+
+    #[test]
+    fn distance_transform_metrics() {
+        let mut ground = Grid::new(7, 7, '#');
+        ground[(3, 3)] = '.';
+
+        assert_eq!(
+            ground.distance_transform(&OrthoDir::ALL, |&c| c == '.'),
+            digits("1111111\n1222221\n1221221\n1210121\n1221221\n1222221\n1111111")
+        );
+        assert_eq!(
+            ground.distance_transform(&DiagDir::ALL, |&c| c == '.'),
+            digits("1111111\n1222221\n1211121\n1210121\n1211121\n1222221\n1111111")
+        );
+    }
+
+    #[test]
+    fn regions_by_connectivity() {
+        let grid = Grid::parse("AAB\nBAB\nBBA");
+        let region = |size, min: (isize, isize), max: (isize, isize), perimeter| Region {
+            size,
+            bounds: Bounds {
+                min: min.into(),
+                max: max.into(),
+            },
+            perimeter,
+        };
+
+        let ortho = grid.regions(&OrthoDir::ALL, |a, b| a == b);
+        assert_eq!(ortho.labels, digits("001\n201\n223"));
+        assert_eq!(
+            ortho.regions,
+            [
+                region(3, (0, 0), (1, 1), 8),
+                region(2, (2, 0), (2, 1), 6),
+                region(3, (0, 1), (1, 2), 8),
+                region(1, (2, 2), (2, 2), 4),
+            ]
+        );
+
+        // Diagonal steps join the lone A in the corner to the other As, and
+        // the Bs on either side of them into one region.
+        let diag = grid.regions(&DiagDir::ALL, |a, b| a == b);
+        assert_eq!(diag.labels, digits("001\n101\n110"));
+        assert_eq!(
+            diag.regions,
+            [region(4, (0, 0), (2, 2), 12), region(5, (0, 0), (2, 2), 14)]
+        );
+    }
+
+    #[test]
+    fn trace_closed_loop() {
+        const LOOP: &str = "###..\n#.###\n#...#\n#####";
+
+        let grid = Grid::parse(LOOP);
+        let trace = grid.trace((0, 0), OrthoDir::East, |&c| c == '#').unwrap();
+
+        assert!(trace.closed);
+        assert_eq!(
+            trace.cells.iter().map(|&(pos, _)| pos).collect::<Vec<_>>(),
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (3, 1),
+                (4, 1),
+                (4, 2),
+                (4, 3),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (0, 3),
+                (0, 2),
+                (0, 1),
+            ]
+            .map(Point2::from)
+        );
+        assert_eq!(trace.clone().into_loop(), Ok(trace));
+
+        // The same loop traced the other way round, from a sparse grid.
+        let sparse = SparseGrid::parse(LOOP, '.');
+        let reversed = sparse.trace((0, 0), OrthoDir::South).unwrap();
+        assert!(reversed.closed);
+        assert_eq!(reversed.cells.len(), 14);
+        assert_eq!(reversed.cells[1].0, Point2::new(0, 1));
+        assert_eq!(reversed.cells[13].0, Point2::new(1, 0));
+    }
+
+    #[test]
+    fn trace_open_path() {
+        let grid = Grid::parse("##.\n.##");
+        let trace = grid.trace((0, 0), OrthoDir::East, |&c| c == '#').unwrap();
+
+        assert!(!trace.closed);
+        assert_eq!(
+            trace.cells.iter().map(|&(pos, _)| pos).collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (1, 1), (2, 1)].map(Point2::from)
+        );
+
+        let error = trace.into_loop().unwrap_err();
+        assert_eq!(error, TraceError::DeadEnd(Point2::new(2, 1)));
+        assert_eq!(error.to_string(), "path comes to a dead end at (2, 1)");
+    }
+
+    #[test]
+    fn trace_fork() {
+        let grid = Grid::parse("###\n.#.");
+        let error = grid
+            .trace((0, 0), OrthoDir::East, |&c| c == '#')
+            .unwrap_err();
+
+        assert_eq!(error, TraceError::Branch(Point2::new(1, 0)));
+        assert_eq!(error.to_string(), "path branches at (1, 0)");
+    }
+
+    #[test]
+    fn trace_from_off_the_path() {
+        let grid = Grid::parse("##.\n.##");
+        let is_path = |&c: &char| c == '#';
+
+        // The start cell itself isn't on the path.
+        assert_eq!(
+            grid.trace((2, 0), OrthoDir::South, is_path).unwrap_err(),
+            TraceError::DeadEnd(Point2::new(2, 0))
+        );
+        assert_eq!(
+            grid.trace((-1, 0), OrthoDir::East, is_path).unwrap_err(),
+            TraceError::DeadEnd(Point2::new(-1, 0))
+        );
+        // Or it is, but the first step leaves the path.
+        assert_eq!(
+            grid.trace((0, 0), OrthoDir::South, is_path).unwrap_err(),
+            TraceError::DeadEnd(Point2::ORIGIN)
+        );
+
+        let sparse = SparseGrid::parse("##.\n.##", '.');
+        assert_eq!(
+            sparse.trace((0, 1), OrthoDir::East).unwrap_err(),
+            TraceError::DeadEnd(Point2::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn all_matches_all_const() {
+        assert!(OrthoDir::all().eq(OrthoDir::ALL));
+        assert!(DiagDir::all().eq(DiagDir::ALL));
+        assert!(HexDir::all().eq(HexDir::ALL));
+    }
+}
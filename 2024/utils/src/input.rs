@@ -0,0 +1,505 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug)]
+pub enum InputError {
+    UnknownFlag(String),
+    MissingValue {
+        flag: String,
+    },
+    DuplicateFlag {
+        part: usize,
+        flag: String,
+    },
+    DuplicateOption {
+        flag: String,
+    },
+    InvalidParts {
+        flag: String,
+        value: String,
+    },
+    InvalidCount {
+        flag: String,
+        value: String,
+    },
+    MissingFile {
+        part: usize,
+        path: PathBuf,
+    },
+    Unreadable {
+        part: usize,
+        path: PathBuf,
+        source: io::Error,
+    },
+    NotUtf8 {
+        part: usize,
+        path: PathBuf,
+    },
+    MalformedAnswers {
+        path: PathBuf,
+        line: usize,
+    },
+    UnreadableAnswers {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::UnknownFlag(flag) => write!(f, "unknown argument `{flag}`"),
+            InputError::MissingValue { flag } => write!(f, "`{flag}` expects a value"),
+            InputError::DuplicateFlag { part, flag } => {
+                write!(
+                    f,
+                    "input for part {part} given more than once (at `{flag}`)"
+                )
+            }
+            InputError::DuplicateOption { flag } => {
+                write!(f, "`{flag}` conflicts with an earlier argument")
+            }
+            InputError::InvalidParts { flag, value } => {
+                write!(
+                    f,
+                    "`{flag}` expects part numbers from 1 to 3, got `{value}`"
+                )
+            }
+            InputError::InvalidCount { flag, value } => {
+                write!(f, "`{flag}` expects a positive number, got `{value}`")
+            }
+            InputError::MissingFile { part, path } => {
+                write!(f, "input for part {part} not found: {}", path.display())
+            }
+            InputError::Unreadable { part, path, source } => {
+                write!(
+                    f,
+                    "could not read input for part {part} from {}: {source}",
+                    path.display()
+                )
+            }
+            InputError::NotUtf8 { part, path } => {
+                write!(
+                    f,
+                    "input for part {part} is not valid UTF-8: {}",
+                    path.display()
+                )
+            }
+            InputError::MalformedAnswers { path, line } => {
+                write!(
+                    f,
+                    "{}:{line}: expected a line of the form `partN: answer`",
+                    path.display()
+                )
+            }
+            InputError::UnreadableAnswers { path, source } => {
+                write!(
+                    f,
+                    "could not read answers from {}: {source}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } => Some(source),
+            InputError::UnreadableAnswers { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub const USAGE: &str = "[--only N | --parts N,M,...] [--time | --bench N] [--dir DIR] \
+    [-p1|--part1 FILE] [-p2|--part2 FILE] [-p3|--part3 FILE] (FILE may be `-` for stdin)";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    fn from_arg(arg: String) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(arg.into())
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Args {
+    pub inputs: [Option<InputSource>; 3],
+    pub dir: Option<PathBuf>,
+    pub parts: Option<[bool; 3]>,
+    pub time: bool,
+    pub bench: Option<usize>,
+}
+
+fn parse_parts(flag: &str, value: &str) -> Result<[bool; 3], InputError> {
+    let invalid = || InputError::InvalidParts {
+        flag: flag.to_string(),
+        value: value.to_string(),
+    };
+
+    let mut parts = [false; 3];
+    for part in value.split(',') {
+        match part.trim().parse::<usize>() {
+            Ok(part @ 1..=3) => parts[part - 1] = true,
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(parts)
+}
+
+fn part_for_flag(flag: &str) -> Option<usize> {
+    match flag {
+        "-p1" | "--part1" => Some(1),
+        "-p2" | "--part2" => Some(2),
+        "-p3" | "--part3" => Some(3),
+        _ => None,
+    }
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, InputError> {
+        let mut args = args.into_iter();
+        let mut parsed = Self::default();
+
+        while let Some(arg) = args.next() {
+            if arg == "--dir" {
+                let Some(dir) = args.next() else {
+                    return Err(InputError::MissingValue { flag: arg });
+                };
+                if parsed.dir.is_some() {
+                    return Err(InputError::DuplicateOption { flag: arg });
+                }
+                parsed.dir = Some(dir.into());
+                continue;
+            }
+
+            if arg == "--only" || arg == "--parts" {
+                let Some(value) = args.next() else {
+                    return Err(InputError::MissingValue { flag: arg });
+                };
+                if arg == "--only" && value.contains(',') {
+                    return Err(InputError::InvalidParts { flag: arg, value });
+                }
+                if parsed.parts.is_some() {
+                    return Err(InputError::DuplicateOption { flag: arg });
+                }
+                parsed.parts = Some(parse_parts(&arg, &value)?);
+                continue;
+            }
+
+            if arg == "--time" {
+                parsed.time = true;
+                continue;
+            }
+
+            if arg == "--bench" {
+                let Some(value) = args.next() else {
+                    return Err(InputError::MissingValue { flag: arg });
+                };
+                if parsed.bench.is_some() {
+                    return Err(InputError::DuplicateOption { flag: arg });
+                }
+                match value.parse() {
+                    Ok(count @ 1..) => parsed.bench = Some(count),
+                    _ => return Err(InputError::InvalidCount { flag: arg, value }),
+                }
+                continue;
+            }
+
+            let Some(part) = part_for_flag(&arg) else {
+                return Err(InputError::UnknownFlag(arg));
+            };
+            let Some(filename) = args.next() else {
+                return Err(InputError::MissingValue { flag: arg });
+            };
+
+            if parsed.inputs[part - 1].is_some() {
+                return Err(InputError::DuplicateFlag { part, flag: arg });
+            }
+            parsed.inputs[part - 1] = Some(InputSource::from_arg(filename));
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn is_selected(&self, part: usize) -> bool {
+        self.parts.is_none_or(|parts| parts[part - 1])
+    }
+
+    pub fn answers_path(&self, default_dir: &Path) -> PathBuf {
+        self.dir
+            .as_deref()
+            .unwrap_or(default_dir)
+            .join("answers.txt")
+    }
+
+    // Recorded answers live next to the inputs, so they're only checked for
+    // parts that weren't given their own input file.
+    pub fn has_own_input(&self, part: usize) -> bool {
+        self.inputs[part - 1].is_some()
+    }
+
+    // Where each part's input should be read from, or None to use the built-in
+    // default. Explicit per-part flags take priority over --dir.
+    pub fn input_sources(&self) -> [Option<InputSource>; 3] {
+        std::array::from_fn(|i| {
+            self.inputs[i].clone().or_else(|| {
+                let dir = self.dir.as_ref()?;
+                Some(InputSource::File(dir.join(format!("part{}.txt", i + 1))))
+            })
+        })
+    }
+}
+
+fn decode_input(
+    part: usize,
+    path: PathBuf,
+    bytes: io::Result<Vec<u8>>,
+) -> Result<String, InputError> {
+    let bytes = match bytes {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(InputError::MissingFile { part, path })
+        }
+        Err(source) => return Err(InputError::Unreadable { part, path, source }),
+    };
+
+    String::from_utf8(bytes).map_err(|_| InputError::NotUtf8 { part, path })
+}
+
+fn read_stdin() -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    io::stdin().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+pub struct Inputs([Option<Cow<'static, str>>; 3]);
+
+impl Inputs {
+    pub fn get(&self, part: usize) -> Option<&str> {
+        self.0[part - 1].as_deref()
+    }
+}
+
+pub fn read_inputs(args: &Args, defaults: [&'static str; 3]) -> Result<Inputs, InputError> {
+    read_inputs_with(args, defaults, read_stdin)
+}
+
+fn read_inputs_with(
+    args: &Args,
+    defaults: [&'static str; 3],
+    mut read_stdin: impl FnMut() -> io::Result<Vec<u8>>,
+) -> Result<Inputs, InputError> {
+    let mut inputs = [None, None, None];
+    // Stdin can only be consumed once, so parts that all ask for it share it.
+    let mut stdin = None;
+
+    for (i, source) in args.input_sources().into_iter().enumerate() {
+        let part = i + 1;
+        if !args.is_selected(part) {
+            continue;
+        }
+
+        inputs[i] = Some(match source {
+            None => Cow::from(defaults[i]),
+            Some(InputSource::Stdin) => {
+                if stdin.is_none() {
+                    stdin = Some(decode_input(part, "<stdin>".into(), read_stdin())?);
+                }
+                Cow::from(stdin.clone().unwrap())
+            }
+            Some(InputSource::File(path)) => {
+                let bytes = std::fs::read(&path);
+                Cow::from(decode_input(part, path, bytes)?)
+            }
+        });
+    }
+
+    Ok(Inputs(inputs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, InputError> {
+        Args::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(matches!(
+            parse(&["--verbose"]),
+            Err(InputError::UnknownFlag(flag)) if flag == "--verbose"
+        ));
+        assert!(matches!(
+            parse(&["part1.txt"]),
+            Err(InputError::UnknownFlag(flag)) if flag == "part1.txt"
+        ));
+        assert!(matches!(
+            parse(&["-p1"]),
+            Err(InputError::MissingValue { flag }) if flag == "-p1"
+        ));
+    }
+
+    #[test]
+    fn input_sources() {
+        let file = |path: &str| Some(InputSource::File(path.into()));
+
+        let table: &[(&[&str], [Option<InputSource>; 3])] = &[
+            (&[], [None, None, None]),
+            (&["-p2", "two.txt"], [None, file("two.txt"), None]),
+            (
+                &["--part1", "-", "-p3", "three.txt"],
+                [Some(InputSource::Stdin), None, file("three.txt")],
+            ),
+            (
+                &["--dir", "inputs"],
+                [
+                    file("inputs/part1.txt"),
+                    file("inputs/part2.txt"),
+                    file("inputs/part3.txt"),
+                ],
+            ),
+            (
+                &["-p2", "-", "--dir", "inputs"],
+                [
+                    file("inputs/part1.txt"),
+                    Some(InputSource::Stdin),
+                    file("inputs/part3.txt"),
+                ],
+            ),
+        ];
+
+        for (args, expected) in table {
+            let sources = parse(args).unwrap().input_sources();
+            assert_eq!(&sources, expected, "{args:?}");
+        }
+    }
+
+    #[test]
+    fn repeated_inputs_are_errors() {
+        assert!(matches!(
+            parse(&["-p2", "a.txt", "--part2", "b.txt"]),
+            Err(InputError::DuplicateFlag { part: 2, flag }) if flag == "--part2"
+        ));
+        assert!(matches!(
+            parse(&["--dir", "a", "--dir", "b"]),
+            Err(InputError::DuplicateOption { flag }) if flag == "--dir"
+        ));
+    }
+
+    #[test]
+    fn part_selection() {
+        let table: &[(&[&str], Option<[bool; 3]>)] = &[
+            (&[], None),
+            (&["--only", "2"], Some([false, true, false])),
+            (&["--parts", "1,3"], Some([true, false, true])),
+            (&["--parts", "3, 1"], Some([true, false, true])),
+            (&["--parts", "2,2"], Some([false, true, false])),
+            (
+                &["--dir", "inputs", "--only", "3"],
+                Some([false, false, true]),
+            ),
+            (
+                &["--parts", "1,2", "--dir", "inputs"],
+                Some([true, true, false]),
+            ),
+        ];
+
+        for (args, expected) in table {
+            let parsed = parse(args).unwrap();
+            assert_eq!(parsed.parts, *expected, "{args:?}");
+            for part in 1..=3 {
+                assert_eq!(
+                    parsed.is_selected(part),
+                    expected.is_none_or(|parts| parts[part - 1]),
+                    "{args:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_part_selections_are_errors() {
+        for args in [
+            &["--only", "1,3"][..],
+            &["--only", "4"],
+            &["--parts", "0,1"],
+            &["--parts", "1,,2"],
+            &["--parts", "two"],
+        ] {
+            assert!(
+                matches!(parse(args), Err(InputError::InvalidParts { .. })),
+                "{args:?}"
+            );
+        }
+
+        assert!(matches!(
+            parse(&["--only", "1", "--parts", "2,3"]),
+            Err(InputError::DuplicateOption { flag }) if flag == "--parts"
+        ));
+    }
+
+    #[test]
+    fn unselected_parts_are_not_read() {
+        let args = parse(&["--only", "2", "-p1", "-", "-p3", "-"]).unwrap();
+
+        let inputs = read_inputs_with(&args, ["one", "two", "three"], || {
+            panic!("stdin was read for an unselected part")
+        })
+        .unwrap();
+
+        assert_eq!(
+            [inputs.get(1), inputs.get(2), inputs.get(3)],
+            [None, Some("two"), None]
+        );
+    }
+
+    #[test]
+    fn stdin_is_read_once_and_shared() {
+        let args = parse(&["-p1", "-", "-p3", "-"]).unwrap();
+
+        let mut reads = 0;
+        let inputs = read_inputs_with(&args, ["one", "two", "three"], || {
+            reads += 1;
+            Ok(b"piped".to_vec())
+        })
+        .unwrap();
+
+        assert_eq!(reads, 1);
+        assert_eq!(
+            [inputs.get(1), inputs.get(2), inputs.get(3)],
+            [Some("piped"), Some("two"), Some("piped")]
+        );
+    }
+
+    #[test]
+    fn missing_input_files_are_errors() {
+        let missing = std::env::temp_dir().join("no-such-quest-input.txt");
+        let args = Args {
+            inputs: [Some(InputSource::File(missing.clone())), None, None],
+            ..Args::default()
+        };
+
+        assert!(matches!(
+            read_inputs(&args, ["", "", ""]),
+            Err(InputError::MissingFile { part: 1, path }) if path == missing
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+// Linear recurrences, where each step replaces every item in a population with
+// a fixed multiset of new items. Stepping is then multiplication by a
+// transition matrix, so many steps can be taken at once by raising the matrix
+// to a power with repeated squaring.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Index, IndexMut},
+};
+
+use crate::counter::Counter;
+
+// How to handle results that don't fit in an i64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arithmetic {
+    // Fail the whole calculation on overflow.
+    Checked,
+    Wrapping,
+    // Work modulo the given (positive) modulus.
+    Modulo(i64),
+}
+
+impl Arithmetic {
+    fn add(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Checked => a.checked_add(b),
+            Arithmetic::Wrapping => Some(a.wrapping_add(b)),
+            Arithmetic::Modulo(m) => Some((a as i128 + b as i128).rem_euclid(modulus(m)) as i64),
+        }
+    }
+
+    fn mul(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Arithmetic::Checked => a.checked_mul(b),
+            Arithmetic::Wrapping => Some(a.wrapping_mul(b)),
+            Arithmetic::Modulo(m) => Some((a as i128 * b as i128).rem_euclid(modulus(m)) as i64),
+        }
+    }
+
+    fn reduce(self, a: i64) -> i64 {
+        match self {
+            Arithmetic::Modulo(m) => (a as i128).rem_euclid(modulus(m)) as i64,
+            _ => a,
+        }
+    }
+}
+
+fn modulus(m: i64) -> i128 {
+    assert!(m > 0, "can't work modulo {m}, the modulus must be positive");
+    m as i128
+}
+
+// A square matrix, indexed by (row, column).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    size: usize,
+    cells: Vec<i64>,
+}
+
+impl Matrix {
+    pub fn zero(size: usize) -> Self {
+        Self {
+            size,
+            cells: vec![0; size * size],
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut matrix = Self::zero(size);
+        for i in 0..size {
+            matrix[(i, i)] = 1;
+        }
+        matrix
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // The matrix product, or None if it overflowed with checked arithmetic.
+    pub fn mul(&self, other: &Self, arithmetic: Arithmetic) -> Option<Self> {
+        assert_eq!(
+            self.size, other.size,
+            "can only multiply matrices of the same size"
+        );
+
+        let mut product = Self::zero(self.size);
+        for row in 0..self.size {
+            for k in 0..self.size {
+                let a = self[(row, k)];
+                if a == 0 {
+                    continue;
+                }
+
+                for col in 0..self.size {
+                    let term = arithmetic.mul(a, other[(k, col)])?;
+                    product[(row, col)] = arithmetic.add(product[(row, col)], term)?;
+                }
+            }
+        }
+
+        Some(product)
+    }
+
+    pub fn pow(&self, mut exponent: u64, arithmetic: Arithmetic) -> Option<Self> {
+        let mut result = Self::identity(self.size);
+        let mut base = self.clone();
+        base.cells
+            .iter_mut()
+            .for_each(|x| *x = arithmetic.reduce(*x));
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(&base, arithmetic)?;
+            }
+
+            exponent >>= 1;
+            // Don't square any further than needed, in case that overflows
+            // when the result itself wouldn't.
+            if exponent > 0 {
+                base = base.mul(&base, arithmetic)?;
+            }
+        }
+
+        Some(result)
+    }
+
+    // The rows and columns at the given indices, in that order.
+    pub fn submatrix(&self, indices: &[usize]) -> Self {
+        let mut submatrix = Self::zero(indices.len());
+        for (row, &i) in indices.iter().enumerate() {
+            for (col, &j) in indices.iter().enumerate() {
+                submatrix[(row, col)] = self[(i, j)];
+            }
+        }
+        submatrix
+    }
+
+    // The product of this matrix with a column vector.
+    pub fn apply(&self, vector: &[i64], arithmetic: Arithmetic) -> Option<Vec<i64>> {
+        assert_eq!(self.size, vector.len(), "vector is the wrong length");
+
+        (0..self.size)
+            .map(|row| {
+                vector.iter().enumerate().try_fold(0, |total, (col, &x)| {
+                    let term = arithmetic.mul(self[(row, col)], arithmetic.reduce(x))?;
+                    arithmetic.add(total, term)
+                })
+            })
+            .collect()
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = i64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < self.size && col < self.size,
+            "({row}, {col}) is outside the matrix"
+        );
+        &self.cells[row * self.size + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            row < self.size && col < self.size,
+            "({row}, {col}) is outside the matrix"
+        );
+        &mut self.cells[row * self.size + col]
+    }
+}
+
+// A population model built from rules saying what each kind of item turns
+// into after one step. Column i of the transition matrix holds what the i-th
+// key produces, so populations are column vectors.
+#[derive(Debug, Clone)]
+pub struct LinearRecurrence<K> {
+    keys: Vec<K>,
+    indices: HashMap<K, usize>,
+    transitions: Matrix,
+}
+
+impl<K: Clone + Hash + Eq> LinearRecurrence<K> {
+    // Builds the model from a rule table mapping each key to the multiset it
+    // becomes. Keys that are produced but have no rule of their own die out.
+    pub fn from_rules<'a>(rules: impl IntoIterator<Item = (&'a K, &'a Counter<K>)>) -> Self
+    where
+        K: 'a,
+    {
+        let mut keys = Vec::new();
+        let mut indices = HashMap::new();
+        let mut index_of = |key: &K| {
+            *indices.entry(key.clone()).or_insert_with(|| {
+                keys.push(key.clone());
+                keys.len() - 1
+            })
+        };
+
+        // The matrix can only be sized once every key has been seen.
+        let mut entries = Vec::new();
+        for (from, produced) in rules {
+            let from = index_of(from);
+            for (to, count) in produced.iter() {
+                entries.push((index_of(to), from, count));
+            }
+        }
+
+        let mut transitions = Matrix::zero(keys.len());
+        for (to, from, count) in entries {
+            transitions[(to, from)] += count;
+        }
+
+        Self {
+            keys,
+            indices,
+            transitions,
+        }
+    }
+
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    pub fn transitions(&self) -> &Matrix {
+        &self.transitions
+    }
+
+    // The population after the given number of steps, or None if it
+    // overflowed with checked arithmetic. Keys in the initial population with
+    // no rules of their own die out after the first step. Only the keys
+    // reachable from the initial population are stepped, so keys it can never
+    // produce don't cause overflow however fast they grow.
+    pub fn population_after(
+        &self,
+        initial: &Counter<K>,
+        steps: u64,
+        arithmetic: Arithmetic,
+    ) -> Option<Counter<K>> {
+        let mut starts = Vec::new();
+        let mut counter = Counter::new();
+        for (key, count) in initial.iter() {
+            match self.indices.get(key) {
+                Some(&i) => starts.push((i, count)),
+                // Nothing's happened to it yet.
+                None if steps == 0 => *counter.entry(key.clone()) += arithmetic.reduce(count),
+                None => {}
+            }
+        }
+
+        let reachable = self.reachable_from(starts.iter().map(|&(i, _)| i));
+        let mut positions = vec![0; self.keys.len()];
+        for (position, &i) in reachable.iter().enumerate() {
+            positions[i] = position;
+        }
+
+        let mut population = vec![0; reachable.len()];
+        for (i, count) in starts {
+            population[positions[i]] = count;
+        }
+
+        let population = self
+            .transitions
+            .submatrix(&reachable)
+            .pow(steps, arithmetic)?
+            .apply(&population, arithmetic)?;
+
+        for (i, count) in reachable.into_iter().zip(population) {
+            *counter.entry(self.keys[i].clone()) += count;
+        }
+
+        Some(counter)
+    }
+
+    // The size of the population after the given number of steps, reduced in
+    // the same way as the counts themselves, or None if it overflowed with
+    // checked arithmetic.
+    pub fn total_after(
+        &self,
+        initial: &Counter<K>,
+        steps: u64,
+        arithmetic: Arithmetic,
+    ) -> Option<i64> {
+        self.population_after(initial, steps, arithmetic)?
+            .iter()
+            .try_fold(0, |total, (_, count)| arithmetic.add(total, count))
+    }
+
+    // The indices of every key that can be produced from the given ones,
+    // including themselves, in the order they're found.
+    fn reachable_from(&self, starts: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        let mut seen = vec![false; self.keys.len()];
+        let mut reachable = Vec::new();
+        for i in starts {
+            if !std::mem::replace(&mut seen[i], true) {
+                reachable.push(i);
+            }
+        }
+
+        let mut next = 0;
+        while let Some(&from) = reachable.get(next) {
+            next += 1;
+            for (to, seen) in seen.iter_mut().enumerate() {
+                if self.transitions[(to, from)] != 0 && !std::mem::replace(seen, true) {
+                    reachable.push(to);
+                }
+            }
+        }
+
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model<'a>(rules: &[(&'a str, &[&'a str])]) -> LinearRecurrence<&'a str> {
+        let rules: HashMap<_, Counter<_>> = rules
+            .iter()
+            .map(|&(key, produced)| (key, produced.iter().copied().collect()))
+            .collect();
+        LinearRecurrence::from_rules(&rules)
+    }
+
+    #[test]
+    fn unreachable_keys_dont_overflow() {
+        let model = model(&[("A", &["A"]), ("B", &["B", "B"])]);
+        let initial = ["A"].into_iter().collect();
+
+        let population = model.population_after(&initial, 70, Arithmetic::Checked);
+        assert_eq!(population, Some(initial.clone()));
+        assert_eq!(
+            model.total_after(&["B"].into_iter().collect(), 70, Arithmetic::Checked),
+            None
+        );
+    }
+
+    #[test]
+    fn modulo_totals_are_reduced() {
+        const M: i64 = 1_000_000_007;
+        let model = model(&[("A", &["A", "B"]), ("B", &["A", "B", "B"])]);
+        let initial = ["A"].into_iter().collect();
+
+        let exact = model
+            .total_after(&initial, 20, Arithmetic::Checked)
+            .unwrap();
+        let reduced = model.total_after(&initial, 20, Arithmetic::Modulo(M));
+        assert_eq!(reduced, Some(exact % M));
+
+        let population = model
+            .population_after(&initial, 20, Arithmetic::Modulo(M))
+            .unwrap();
+        assert!(population.iter().all(|(_, count)| (0..M).contains(&count)));
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus must be positive")]
+    fn modulo_zero_is_rejected() {
+        let model = model(&[("A", &["A", "A"])]);
+        model.population_after(&["A"].into_iter().collect(), 3, Arithmetic::Modulo(0));
+    }
+
+    #[test]
+    fn keys_without_rules_die_out() {
+        let model = model(&[("A", &["A", "B"])]);
+        let initial: Counter<_> = ["A", "B", "C", "C"].into_iter().collect();
+
+        assert_eq!(
+            model.population_after(&initial, 0, Arithmetic::Checked),
+            Some(initial.clone())
+        );
+        assert_eq!(
+            model.population_after(&initial, 1, Arithmetic::Checked),
+            Some(["A", "B"].into_iter().collect())
+        );
+        assert_eq!(
+            model.total_after(&["C"].into_iter().collect(), 5, Arithmetic::Checked),
+            Some(0)
+        );
+        assert_eq!(
+            model.population_after(&initial, 0, Arithmetic::Modulo(2)),
+            Some(["A", "B"].into_iter().collect())
+        );
+    }
+
+    #[test]
+    fn steps_compose() {
+        let model = model(&[
+            ("A", &["B"]),
+            ("B", &["A", "C", "C"]),
+            ("C", &["A", "B", "C"]),
+            ("D", &["D", "D"]),
+        ]);
+        let initial: Counter<_> = ["A", "C", "C"].into_iter().collect();
+
+        let mut population = initial.clone();
+        for steps in 1..=12 {
+            population = model
+                .population_after(&population, 1, Arithmetic::Checked)
+                .unwrap();
+            assert_eq!(
+                model.population_after(&initial, steps, Arithmetic::Checked),
+                Some(population.clone()),
+                "after {steps} steps"
+            );
+        }
+    }
+}
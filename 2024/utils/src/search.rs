@@ -0,0 +1,330 @@
+// Graph searches over implicit graphs, where the edges out of each state are
+// given by a neighbours function. States can be anything hashable: grid
+// positions, or more abstract puzzle states.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// The distances to every state reached by a search, along with the state each
+// one was first reached from.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    pub distances: HashMap<S, C>,
+    pub predecessors: HashMap<S, S>,
+}
+
+impl<S, C> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    // The path from whichever start state target was reached from, to target
+    // itself (inclusive of both ends).
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(target) {
+            return None;
+        }
+
+        Some(reconstruct_path(&self.predecessors, target.clone()))
+    }
+}
+
+fn reconstruct_path<S: Clone + Eq + Hash>(predecessors: &HashMap<S, S>, target: S) -> Vec<S> {
+    let mut path = vec![target];
+    while let Some(prev) = predecessors.get(path.last().unwrap()) {
+        path.push(prev.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+// Breadth-first search where every edge has cost 1. Starting from several
+// states at once gives the distance from each state to its nearest start.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state];
+
+        for next in neighbours(&state) {
+            if distances.contains_key(&next) {
+                continue;
+            }
+
+            distances.insert(next.clone(), distance + 1);
+            predecessors.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+
+    SearchResult {
+        distances,
+        predecessors,
+    }
+}
+
+// A min-heap entry ordered only by its priority, so that states themselves
+// don't need to be Ord.
+struct Entry<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, since BinaryHeap is a max-heap.
+        other.priority.cmp(&self.priority)
+    }
+}
+
+// Finds the cheapest distance from the nearest start to every reachable state.
+// Edge costs must be non-negative, and C::default() is taken as zero.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        distances.insert(start.clone(), C::default());
+        heap.push(Entry {
+            priority: C::default(),
+            cost: C::default(),
+            state: start,
+        });
+    }
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if distances.get(&state).is_some_and(|&best| cost > best) {
+            // Already found a cheaper way here.
+            continue;
+        }
+
+        for (next, edge_cost) in neighbours(&state) {
+            let next_cost = cost + edge_cost;
+            if distances.get(&next).is_some_and(|&best| next_cost >= best) {
+                continue;
+            }
+
+            distances.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), state.clone());
+            heap.push(Entry {
+                priority: next_cost,
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    SearchResult {
+        distances,
+        predecessors,
+    }
+}
+
+// Finds the cheapest path from start to any state satisfying is_goal,
+// returning its cost and the path itself. The heuristic must never
+// overestimate the remaining cost to a goal, or the path found may not be the
+// cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Option<(C, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    distances.insert(start.clone(), C::default());
+    heap.push(Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    });
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if distances.get(&state).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        if is_goal(&state) {
+            return Some((cost, reconstruct_path(&predecessors, state)));
+        }
+
+        for (next, edge_cost) in neighbours(&state) {
+            let next_cost = cost + edge_cost;
+            if distances.get(&next).is_some_and(|&best| next_cost >= best) {
+                continue;
+            }
+
+            distances.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), state.clone());
+            heap.push(Entry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, OrthoDir, Point2};
+
+    const MAZE: &str = "\
+#######
+#S...E#
+#.###.#
+#...#.#
+###.#.#
+#..X#.#
+#######";
+
+    fn open_neighbours(grid: &Grid<char>, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+        OrthoDir::ALL
+            .into_iter()
+            .map(move |dir| dir.step(pos))
+            .filter(|&next| grid.get(next).is_some_and(|&c| c != '#'))
+    }
+
+    #[test]
+    fn bfs_from_several_starts() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let result = bfs([start, end], |&pos| open_neighbours(&grid, pos));
+
+        assert_eq!(result.distance(&start), Some(0));
+        assert_eq!(result.distance(&end), Some(0));
+        // X is only reachable from S, and the right-hand corridor only from E.
+        assert_eq!(result.distance(&grid.find(&'X').unwrap()), Some(6));
+        assert_eq!(result.distance(&Point2::new(5, 5)), Some(4));
+
+        let path = result.path_to(&Point2::new(5, 5)).unwrap();
+        assert_eq!(path.first(), Some(&end));
+        assert_eq!(path.len(), 5);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_longer_paths() {
+        // a -> b -> c -> d is cheaper than the direct a -> d edge.
+        let edges = |&state: &char| -> Vec<(char, u32)> {
+            match state {
+                'a' => vec![('b', 1), ('d', 10)],
+                'b' => vec![('c', 2)],
+                'c' => vec![('d', 3)],
+                _ => vec![],
+            }
+        };
+
+        let result = dijkstra(['a'], edges);
+
+        assert_eq!(result.distance(&'d'), Some(6));
+        assert_eq!(result.path_to(&'d'), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(result.distance(&'z'), None);
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(&'S').unwrap();
+        // Walking into a cell costs its column number plus one, so the
+        // cheapest route isn't just the shortest one.
+        let weighted = |&pos: &Point2| {
+            open_neighbours(&grid, pos)
+                .map(|next| (next, next.x as usize + 1))
+                .collect::<Vec<_>>()
+        };
+
+        let distances = dijkstra([start], weighted);
+
+        for target in grid.positions().filter(|&pos| grid[pos] != '#') {
+            let (cost, path) = astar(
+                start,
+                |&pos| pos == target,
+                weighted,
+                |&pos| pos.manhattan(target),
+            )
+            .unwrap();
+
+            assert_eq!(Some(cost), distances.distance(&target), "{target:?}");
+            assert_eq!(path.first(), Some(&start));
+            assert_eq!(path.last(), Some(&target));
+        }
+    }
+
+    #[test]
+    fn unreachable_states_have_no_path() {
+        let grid = Grid::parse("S.#.\n..#.");
+        let start = grid.find(&'S').unwrap();
+        let beyond_wall = Point2::new(3, 0);
+
+        let result = bfs([start], |&pos| open_neighbours(&grid, pos));
+        assert_eq!(result.distance(&beyond_wall), None);
+        assert_eq!(result.path_to(&beyond_wall), None);
+
+        let weighted = |&pos: &Point2| open_neighbours(&grid, pos).map(|next| (next, 1));
+        assert_eq!(
+            astar(start, |&pos| pos == beyond_wall, weighted, |_| 0),
+            None
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::Answers,
+    input::{read_inputs, Args, InputError, USAGE},
+};
+
+pub trait Solution {
+    type Input<'a>;
+
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    fn part1(input: &Self::Input<'_>) -> impl Display;
+    fn part2(input: &Self::Input<'_>) -> impl Display;
+    fn part3(input: &Self::Input<'_>) -> impl Display;
+}
+
+// A type-erased handle to a quest's solution and its default inputs, so that
+// quests can be collected together and run from a single binary.
+#[derive(Clone, Copy)]
+pub struct Quest {
+    pub number: u32,
+    pub dir: &'static str,
+    pub inputs: [&'static str; 3],
+    solve: fn(usize, &str) -> (String, Timings),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+fn solve<S: Solution>(part: usize, input: &str) -> (String, Timings) {
+    let start = Instant::now();
+    let input = S::parse(input);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        1 => S::part1(&input).to_string(),
+        2 => S::part2(&input).to_string(),
+        3 => S::part3(&input).to_string(),
+        _ => panic!("quests only have parts 1 to 3, got part {part}"),
+    };
+    let solve = start.elapsed();
+
+    (answer, Timings { parse, solve })
+}
+
+fn print_stats(label: &str, samples: impl Iterator<Item = Duration>) {
+    let mut samples: Vec<_> = samples.collect();
+    samples.sort();
+
+    let min = samples[0];
+    let median = samples[samples.len() / 2];
+    let max = samples[samples.len() - 1];
+
+    println!("  {label}: min {min:.2?}, median {median:.2?}, max {max:.2?}");
+}
+
+impl Quest {
+    pub const fn new<S: Solution>(
+        number: u32,
+        dir: &'static str,
+        inputs: [&'static str; 3],
+    ) -> Self {
+        Self {
+            number,
+            dir,
+            inputs,
+            solve: solve::<S>,
+        }
+    }
+
+    pub fn solve(&self, part: usize, input: &str) -> (String, Timings) {
+        (self.solve)(part, input)
+    }
+
+    // Solves and prints each part selected by args, along with how long it
+    // took if asked to time or benchmark it. Answers are checked against any
+    // that have been recorded, and the number of mismatches is returned.
+    pub fn run(&self, args: &Args) -> Result<usize, InputError> {
+        let inputs = read_inputs(args, self.inputs)?;
+        let answers = Answers::load(&args.answers_path(Path::new(self.dir)))?;
+        let mut mismatches = 0;
+
+        for part in 1..=3 {
+            let Some(input) = inputs.get(part) else {
+                continue;
+            };
+            let (answer, timings) = self.solve(part, input);
+
+            let expected = answers.get(part).filter(|_| !args.has_own_input(part));
+            let verdict = match expected {
+                None => String::new(),
+                Some(expected) if expected == answer => " ✓".to_string(),
+                Some(expected) => {
+                    mismatches += 1;
+                    format!(" ✗ (expected {expected})")
+                }
+            };
+
+            if let Some(runs) = args.bench {
+                let mut samples = vec![timings];
+                samples.extend((1..runs).map(|_| self.solve(part, input).1));
+
+                println!("{answer}{verdict}");
+                print_stats("parse", samples.iter().map(|t| t.parse));
+                print_stats("solve", samples.iter().map(|t| t.solve));
+            } else if args.time {
+                println!(
+                    "{answer}{verdict} (parse: {:.2?}, solve: {:.2?})",
+                    timings.parse, timings.solve
+                );
+            } else {
+                println!("{answer}{verdict}");
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
+pub fn exit_with_usage(err: impl Display, usage: &str) -> ! {
+    eprintln!("error: {err}");
+    eprintln!("usage: {usage}");
+    std::process::exit(1)
+}
+
+// Entry point for a single quest's binary.
+pub fn main(quest: &Quest) {
+    let result = Args::parse(std::env::args().skip(1)).and_then(|args| quest.run(&args));
+
+    match result {
+        Ok(0) => (),
+        Ok(_) => std::process::exit(1),
+        Err(e) => exit_with_usage(e, USAGE),
+    }
+}
+
+// Defines a quest's QUEST handle, and tests that check each part against its
+// example. Parts whose examples can't be checked this way must be listed with a
+// reason, and their tests are ignored:
+//
+//     utils::quest!(7, Quest7, ignore { part2: "no example track" });
+#[macro_export]
+macro_rules! quest {
+    // Sorts the ignored parts into one slot each, then emits the tests.
+    (@examples [$($r1:literal)?] [$($r2:literal)?] [$($r3:literal)?]) => {
+        #[cfg(test)]
+        mod examples {
+            $crate::quest!(@example part1, 1 $(, $r1)?);
+            $crate::quest!(@example part2, 2 $(, $r2)?);
+            $crate::quest!(@example part3, 3 $(, $r3)?);
+        }
+    };
+    (@examples [] [$($r2:literal)?] [$($r3:literal)?] part1: $r:literal $(, $($rest:tt)*)?) => {
+        $crate::quest!(@examples [$r] [$($r2)?] [$($r3)?] $($($rest)*)?);
+    };
+    (@examples [$($r1:literal)?] [] [$($r3:literal)?] part2: $r:literal $(, $($rest:tt)*)?) => {
+        $crate::quest!(@examples [$($r1)?] [$r] [$($r3)?] $($($rest)*)?);
+    };
+    (@examples [$($r1:literal)?] [$($r2:literal)?] [] part3: $r:literal $(, $($rest:tt)*)?) => {
+        $crate::quest!(@examples [$($r1)?] [$($r2)?] [$r] $($($rest)*)?);
+    };
+
+    (@example $name:ident, $part:literal) => {
+        #[test]
+        fn $name() {
+            $crate::testing::check_example(&super::QUEST, $part);
+        }
+    };
+    (@example $name:ident, $part:literal, $reason:literal) => {
+        #[test]
+        #[ignore = $reason]
+        fn $name() {
+            $crate::testing::check_example(&super::QUEST, $part);
+        }
+    };
+
+    ($number:expr, $solution:ty $(, ignore { $($ignored:tt)* })?) => {
+        pub const QUEST: $crate::Quest = $crate::Quest::new::<$solution>(
+            $number,
+            env!("CARGO_MANIFEST_DIR"),
+            [
+                include_str!("../part1.txt"),
+                include_str!("../part2.txt"),
+                include_str!("../part3.txt"),
+            ],
+        );
+
+        $crate::quest!(@examples [] [] [] $($($ignored)*)?);
+    };
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use crate::{answers::Answers, Quest};
+
+// Runs one part of a quest on the worked example from the puzzle text. Each
+// quest keeps these in `examples/partN.txt`, with the expected answers in
+// `examples/answers.txt` using the same format as the answer store. Parts
+// without an expected answer fail, so that they can't pass unnoticed; quest!
+// can mark their tests as ignored instead.
+pub fn check_example(quest: &Quest, part: usize) {
+    let dir = Path::new(quest.dir).join("examples");
+    let answers = Answers::load(&dir.join("answers.txt")).unwrap_or_else(|e| panic!("{e}"));
+
+    let Some(expected) = answers.get(part) else {
+        panic!(
+            "quest {} part {part} has no example answer in {}",
+            quest.number,
+            dir.display()
+        );
+    };
+
+    let path = dir.join(format!("part{part}.txt"));
+    let input = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()));
+
+    let (answer, _) = quest.solve(part, &input);
+    assert_eq!(
+        answer, expected,
+        "quest {} part {part} gave the wrong answer for its example",
+        quest.number
+    );
+}